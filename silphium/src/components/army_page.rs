@@ -0,0 +1,339 @@
+use implicit_clone::unsync::IString;
+use serde::{Deserialize, Serialize};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+
+use crate::{
+    AppContext,
+    components::{BackLink, Button, RosterFilter, Text, UnitFilter, class_title},
+    hooks::{ModelHandle, use_messages, use_query_model},
+    model::{Faction, Unit, UnitClass},
+};
+
+/// The default slot limit, the size of a full army stack.
+const DEFAULT_SLOTS: u32 = 20;
+
+/// An army as kept in the query string, so that it can be shared as a link.
+#[derive(PartialEq, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Army {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub era: Option<AttrValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub horde: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub budget: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slots: Option<u32>,
+    /// The ids of the units in the army, separated by commas.
+    pub u: String,
+}
+
+impl Army {
+    pub fn units(&self) -> Vec<IString> {
+        self.u
+            .split(',')
+            .filter(|id| !id.is_empty())
+            .map(|id| IString::from(id.to_string()))
+            .collect()
+    }
+
+    pub fn with_units(&self, units: &[IString]) -> Self {
+        Self {
+            u: units
+                .iter()
+                .map(|id| id.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+            ..self.clone()
+        }
+    }
+
+    pub fn slots(&self) -> u32 {
+        self.slots.unwrap_or(DEFAULT_SLOTS)
+    }
+}
+
+/// The units of an army, each with how many times it was picked, in the order
+/// they were first picked.
+fn army_entries(faction: &Faction, ids: &[IString]) -> Vec<(Unit, u32)> {
+    let mut entries: Vec<(Unit, u32)> = vec![];
+    for id in ids {
+        if let Some(entry) = entries.iter_mut().find(|(u, _)| &u.id == id) {
+            entry.1 += 1;
+        } else if let Some(unit) = faction.roster.iter().find(|u| &u.id == id) {
+            entries.push((unit, 1));
+        }
+    }
+    entries
+}
+
+fn one_line(text: &str) -> String {
+    text.replace('\n', " ")
+}
+
+/// The army as plain text or BBCode, for posting elsewhere.
+fn export_army(faction: &Faction, entries: &[(Unit, u32)], bbcode: bool) -> String {
+    let count: u32 = entries.iter().map(|(_, n)| n).sum();
+    let cost: u32 = entries.iter().map(|(u, n)| u.cost * n).sum();
+    let upkeep: u32 = entries.iter().map(|(u, n)| u.upkeep * n).sum();
+    let title = one_line(&faction.name);
+    let summary = format!("{count} units, cost {cost}, upkeep {upkeep}");
+    let lines = entries
+        .iter()
+        .map(|(u, n)| format!("{n}× {}", one_line(&u.name)));
+    if bbcode {
+        let items: String = lines.map(|l| format!("[*]{l}\n")).collect();
+        format!("[b]{title}[/b]\n[list]\n{items}[/list]\n[i]{summary}[/i]\n")
+    } else {
+        let items: String = lines.map(|l| format!("{l}\n")).collect();
+        format!("{title}\n{items}{summary}\n")
+    }
+}
+
+#[autoprops]
+#[function_component(ArmyPage)]
+pub fn army_page(module_id: AttrValue, faction_id: AttrValue) -> Html {
+    let ctx = use_context::<AppContext>().expect("no context");
+    let module = &ctx.modules[&module_id];
+    let aliases = &module.aliases;
+    let faction_id = aliases.get(&faction_id).unwrap_or(&faction_id);
+    let faction = module.factions.get(faction_id).unwrap();
+    let t = use_messages();
+
+    let army = use_query_model(|| Army {
+        era: (faction.eras.len() > 1).then(|| faction.eras[0].clone()),
+        horde: faction.is_horde.then_some(false),
+        ..Default::default()
+    });
+    let refine = use_state(UnitFilter::default);
+    let bbcode = use_state(|| false);
+
+    // The era and horde choices are part of the army, the other filters are not
+    let filter = {
+        let army = army.clone();
+        let refine = refine.clone();
+        ModelHandle::new(
+            UnitFilter {
+                era: army.era.clone(),
+                horde: army.horde,
+                ..(*refine).clone()
+            },
+            move |f: UnitFilter| {
+                army.set(Army {
+                    era: f.era.clone(),
+                    horde: f.horde,
+                    ..(*army).clone()
+                });
+                refine.set(f);
+            },
+        )
+    };
+
+    let ids = army.units();
+    let entries = army_entries(faction, &ids);
+    let count = ids.len() as u32;
+    let cost: u32 = entries.iter().map(|(u, n)| u.cost * n).sum();
+    let upkeep: u32 = entries.iter().map(|(u, n)| u.upkeep * n).sum();
+    let slots = army.slots();
+    let remaining = army.budget.map(|b| b as i64 - cost as i64);
+
+    let set_units = {
+        let army = army.clone();
+        Callback::from(move |units: Vec<IString>| army.set(army.with_units(&units)))
+    };
+    let add_unit = |id: &IString| {
+        let ids = ids.clone();
+        let set_units = set_units.clone();
+        let id = id.clone();
+        Callback::from(move |_: MouseEvent| {
+            let mut ids = ids.clone();
+            ids.push(id.clone());
+            set_units.emit(ids);
+        })
+    };
+
+    let picker = faction
+        .roster
+        .iter()
+        .filter(|u| filter.apply(u))
+        .map(|unit| {
+            let affordable = remaining.is_none_or(|r| unit.cost as i64 <= r);
+            let disabled = count >= slots || !affordable;
+            let onclick = add_unit(&unit.id);
+            html! {
+              <tr>
+                <td class="name"><Text text={&unit.name} /></td>
+                <td>{ t.get(class_title(unit.class)) }</td>
+                <td class="number">{ unit.cost }</td>
+                <td class="number">{ unit.upkeep }</td>
+                <td><button {disabled} {onclick}>{t.get("Add")}</button></td>
+              </tr>
+            }
+        });
+
+    let rows = entries.iter().map(|(unit, n)| {
+        let add = add_unit(&unit.id);
+        let remove = {
+            let ids = ids.clone();
+            let set_units = set_units.clone();
+            let id = unit.id.clone();
+            Callback::from(move |_: MouseEvent| {
+                let mut ids = ids.clone();
+                if let Some(i) = ids.iter().rposition(|u| u == &id) {
+                    ids.remove(i);
+                }
+                set_units.emit(ids);
+            })
+        };
+        let affordable = remaining.is_none_or(|r| unit.cost as i64 <= r);
+        html! {
+          <tr>
+            <td class="number">{ format!("{n}×") }</td>
+            <td class="name"><Text text={&unit.name} /></td>
+            <td class="number">{ unit.cost * n }</td>
+            <td class="number">{ unit.upkeep * n }</td>
+            <td>
+              <button onclick={remove}>{"−"}</button>
+              <button disabled={count >= slots || !affordable} onclick={add}>{"+"}</button>
+            </td>
+          </tr>
+        }
+    });
+
+    let composition = UnitClass::all().into_iter().filter_map(|class| {
+        let n: u32 = entries
+            .iter()
+            .filter(|(u, _)| u.class == class)
+            .map(|(_, n)| n)
+            .sum();
+        (n > 0).then(|| {
+            html! {
+              <li>
+                <span class="name">{ t.get(class_title(class)) }</span>
+                <span class="number">{ n }</span>
+              </li>
+            }
+        })
+    });
+
+    let on_budget = {
+        let army = army.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            army.set(Army {
+                budget: input.value().parse().ok(),
+                ..(*army).clone()
+            });
+        })
+    };
+    let on_slots = {
+        let army = army.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            army.set(Army {
+                slots: input.value().parse().ok().filter(|s| *s != DEFAULT_SLOTS),
+                ..(*army).clone()
+            });
+        })
+    };
+    let on_format = {
+        let bbcode = bbcode.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            bbcode.set(select.value() == "bbcode");
+        })
+    };
+    let on_clear = {
+        let set_units = set_units.clone();
+        Callback::from(move |()| set_units.emit(vec![]))
+    };
+
+    let over_budget = remaining.is_some_and(|r| r < 0);
+    let export = export_army(faction, &entries, *bbcode);
+
+    html! {
+    <div class="army-page">
+      <header class="header-container">
+        <div class="nav">
+          <BackLink />
+        </div>
+        <div class="faction-header header">
+          <div class="title">
+            <div class="name"><Text text={&faction.name} /></div>
+            <RosterFilter {module} {faction} filter={filter.clone()} />
+          </div>
+          <img class="icon" src={&faction.image} />
+        </div>
+      </header>
+      <main>
+        <section class="army">
+          <h2>{t.get("Army")}</h2>
+          <div class="limits">
+            <label>
+              <span>{t.get("Budget")}</span>
+              <input type="number" min="0" placeholder={t.get("none")}
+                  value={army.budget.map(|b| b.to_string()).unwrap_or_default()}
+                  onchange={on_budget} />
+            </label>
+            <label>
+              <span>{t.get("Slots")}</span>
+              <input type="number" min="1" value={slots.to_string()} onchange={on_slots} />
+            </label>
+          </div>
+          <div class="totals">
+            <span class={classes!("slots", (count > slots).then_some("over"))}>
+              { t.format("{}/{} units", &[&count, &slots]) }
+            </span>
+            <span class={classes!("cost", over_budget.then_some("over"))}>
+              if let Some(budget) = army.budget {
+                { t.format("Cost {} of {}", &[&cost, &budget]) }
+              } else {
+                { t.format("Cost {}", &[&cost]) }
+              }
+            </span>
+            <span class="upkeep">{ t.format("Upkeep {}", &[&upkeep]) }</span>
+          </div>
+          if entries.is_empty() {
+            <p class="hint">{t.get("Add units from the roster.")}</p>
+          } else {
+            <table class="army-units">
+              <tbody>
+                {for rows}
+              </tbody>
+            </table>
+            <ul class="composition">
+              {for composition}
+            </ul>
+            <Button class="clear" title={t.get("Remove all units")} onclick={on_clear}>{t.get("Clear")}</Button>
+            <div class="export">
+              <select onchange={on_format}>
+                <option value="text" selected={!*bbcode}>{t.get("Text")}</option>
+                <option value="bbcode" selected={*bbcode}>{"BBCode"}</option>
+              </select>
+              <textarea readonly={true} rows="8" value={export} />
+            </div>
+          }
+        </section>
+        <section class="picker">
+          <h2>{t.get("Roster")}</h2>
+          <table class="roster-units">
+            <thead>
+              <tr>
+                <th>{t.get("Unit")}</th>
+                <th>{t.get("Class")}</th>
+                <th>{t.get("Cost")}</th>
+                <th>{t.get("Upkeep")}</th>
+                <th></th>
+              </tr>
+            </thead>
+            <tbody>
+              {for picker}
+            </tbody>
+          </table>
+        </section>
+      </main>
+    </div>
+    }
+}
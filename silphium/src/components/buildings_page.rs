@@ -0,0 +1,146 @@
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+
+use crate::{
+    AppContext,
+    components::{BackLink, Link, Text},
+    hooks::use_messages,
+    model::{BuildingChain, BuildingLevel, Module, Recruit},
+    routes::Route,
+};
+
+/// The anchor of a building level on the buildings page.
+fn level_anchor(id: &str) -> String {
+    format!("level-{id}")
+}
+
+#[autoprops]
+#[function_component(BuildingsPage)]
+pub fn buildings_page(module_id: AttrValue) -> Html {
+    let ctx = use_context::<AppContext>().expect("no context");
+    let module = &ctx.modules[&module_id];
+    let t = use_messages();
+
+    let index = module.buildings.iter().map(|chain| {
+        let href = format!("#chain-{}", chain.id);
+        html! { <li><a {href}>{ &chain.id }</a></li> }
+    });
+    let chains = module.buildings.iter().map(|chain| {
+        html! { <BuildingChainSection {module} {chain} /> }
+    });
+
+    html! {
+    <div class="buildings-page">
+      <header class="header-container">
+        <div class="nav">
+          <BackLink />
+        </div>
+        <div class="header">
+          <div class="name">{t.get("Buildings")}</div>
+        </div>
+      </header>
+      <main>
+        <ul class="chain-index">
+          {for index}
+        </ul>
+        {for chains}
+      </main>
+    </div>
+    }
+}
+
+#[autoprops]
+#[function_component(BuildingChainSection)]
+fn building_chain_section(module: Module, chain: BuildingChain) -> Html {
+    let levels = chain.levels.iter().map(|level| {
+        html! { <BuildingLevelCard module={&module} {level} /> }
+    });
+    html! {
+      <section class="building-chain" id={format!("chain-{}", chain.id)}>
+        <h2>{ &chain.id }</h2>
+        <div class="levels">
+          {for levels}
+        </div>
+      </section>
+    }
+}
+
+#[autoprops]
+#[function_component(BuildingLevelCard)]
+fn building_level_card(module: Module, level: BuildingLevel) -> Html {
+    let t = use_messages();
+    let mut details = vec![format!("{}+", level.min)];
+    if level.cost > 0 {
+        details.push(t.format("cost {}", &[&level.cost]));
+    }
+    if level.turns > 0 {
+        details.push(t.format("{} turns", &[&level.turns]));
+    }
+
+    let upgrades = level.upgrades.iter().map(|id| {
+        let href = format!("#{}", level_anchor(&id));
+        html! { <li><a {href}>{ id }</a></li> }
+    });
+    let capabilities = level.capabilities.iter().map(|c| html! { <li>{ c }</li> });
+    let recruits = level
+        .recruits
+        .iter()
+        .map(|recruit| html! { <RecruitRow module={&module} {recruit} /> });
+
+    html! {
+      <div class="building-level" id={level_anchor(&level.id)}>
+        <div class="title">
+          <span class="name"><Text text={&level.name} /></span>
+          <span class="id">{ &level.id }</span>
+        </div>
+        <div class="details">{ details.join(", ") }</div>
+        if !level.description.is_empty() {
+          <p class="description"><Text text={&level.description} /></p>
+        }
+        if !level.recruits.is_empty() {
+          <h3>{t.get("Recruits")}</h3>
+          <ul class="recruits">
+            {for recruits}
+          </ul>
+        }
+        if !level.capabilities.is_empty() {
+          <h3>{t.get("Capabilities")}</h3>
+          <ul class="capabilities">
+            {for capabilities}
+          </ul>
+        }
+        if !level.upgrades.is_empty() {
+          <h3>{t.get("Upgrades to")}</h3>
+          <ul class="upgrades">
+            {for upgrades}
+          </ul>
+        }
+      </div>
+    }
+}
+
+#[autoprops]
+#[function_component(RecruitRow)]
+fn recruit_row(module: Module, recruit: Recruit) -> Html {
+    let factions = recruit.factions.iter().filter_map(|id| {
+        let faction = module.factions.get(&id)?;
+        let to = Route::Unit {
+            module: module.id.clone(),
+            faction: faction.id_or_alias(),
+            unit: recruit.unit.clone(),
+        };
+        Some(html! {
+          <Link {to}>
+            <img class="icon" src={&faction.image} title={&faction.name} />
+          </Link>
+        })
+    });
+    html! {
+      <li>
+        <span class="name"><Text text={&recruit.name} /></span>
+        <span class="factions">
+          {for factions}
+        </span>
+      </li>
+    }
+}
@@ -0,0 +1,316 @@
+use implicit_clone::unsync::IString;
+use serde::{Deserialize, Serialize};
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+use yew_router::prelude::*;
+
+use crate::{
+    AppContext, Messages,
+    components::{BackLink, Button, Text, UnitCard},
+    hooks::use_messages,
+    model::{Module, Unit},
+    routes::{MERCS, Route},
+};
+
+#[derive(PartialEq, Clone, Default, Serialize, Deserialize)]
+struct CompareQuery {
+    #[serde(default)]
+    u: String,
+}
+
+impl CompareQuery {
+    fn picks(&self) -> Vec<(IString, IString)> {
+        self.u
+            .split(',')
+            .filter_map(|p| p.split_once(':'))
+            .map(|(source, unit)| (IString::from(source.to_string()), unit.to_string().into()))
+            .collect()
+    }
+
+    fn from_picks(picks: &[(IString, IString)]) -> Self {
+        Self {
+            u: picks
+                .iter()
+                .map(|(source, unit)| format!("{source}:{unit}"))
+                .collect::<Vec<_>>()
+                .join(","),
+        }
+    }
+}
+
+struct Stat {
+    name: &'static str,
+    value: fn(&Unit) -> Option<u32>,
+    lower_is_better: bool,
+}
+
+const STATS: [Stat; 7] = [
+    Stat {
+        name: "Attack",
+        value: |u| u.primary_weapon.as_ref().map(|w| w.factor),
+        lower_is_better: false,
+    },
+    Stat {
+        name: "Charge",
+        value: |u| u.primary_weapon.as_ref().map(|w| w.charge),
+        lower_is_better: false,
+    },
+    Stat {
+        name: "Defense",
+        value: |u| Some(u.defense.total()),
+        lower_is_better: false,
+    },
+    Stat {
+        name: "Morale",
+        value: |u| Some(u.morale),
+        lower_is_better: false,
+    },
+    Stat {
+        name: "Cost",
+        value: |u| Some(u.cost),
+        lower_is_better: true,
+    },
+    Stat {
+        name: "Upkeep",
+        value: |u| Some(u.upkeep),
+        lower_is_better: true,
+    },
+    Stat {
+        name: "Speed",
+        value: |u| u.move_speed,
+        lower_is_better: false,
+    },
+];
+
+fn source_name(t: &Messages, module: &Module, source: &str) -> IString {
+    if source == MERCS {
+        t.get("Mercenaries")
+    } else {
+        module
+            .factions
+            .get(source)
+            .map(|f| f.name.clone())
+            .unwrap_or_default()
+    }
+}
+
+fn source_units(module: &Module, source: &str) -> Vec<Unit> {
+    if source == MERCS {
+        let mut units: Vec<Unit> = vec![];
+        for entry in module.pools.iter().flat_map(|p| p.units.iter()) {
+            if !units.iter().any(|u| u.id == entry.unit.id) {
+                units.push(entry.unit.clone());
+            }
+        }
+        units
+    } else {
+        module
+            .factions
+            .get(source)
+            .map(|f| f.roster.iter().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[autoprops]
+#[function_component(ComparePage)]
+pub fn compare_page(module_id: AttrValue) -> Html {
+    let ctx = use_context::<AppContext>().expect("no context");
+    let module = &ctx.modules[&module_id];
+    let t = &use_messages();
+    let navigator = use_navigator();
+    let query = use_location()
+        .and_then(|l| l.query::<CompareQuery>().ok())
+        .unwrap_or_default();
+    let picks = query.picks();
+
+    let set_picks = {
+        let route = Route::Compare {
+            module: module.id.clone(),
+        };
+        Callback::from(move |picks: Vec<(IString, IString)>| {
+            if let Some(ref navigator) = navigator {
+                let _ = navigator.replace_with_query(&route, &CompareQuery::from_picks(&picks));
+            }
+        })
+    };
+
+    let units: Vec<_> = picks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (source, id))| {
+            source_units(module, source)
+                .into_iter()
+                .find(|u| &u.id == id)
+                .map(|u| (i, source.clone(), u))
+        })
+        .collect();
+
+    let columns: Vec<_> = units
+        .iter()
+        .map(|(i, source, unit)| {
+            let onclick = {
+                let picks = picks.clone();
+                let set_picks = set_picks.clone();
+                let i = *i;
+                Callback::from(move |()| {
+                    let mut picks = picks.clone();
+                    picks.remove(i);
+                    set_picks.emit(picks);
+                })
+            };
+            html! {
+              <div class="compare-column">
+                <div class="source"><Text text={source_name(t, module, source)} /></div>
+                <UnitCard {unit} />
+                <Button class="remove" title={t.get("Remove from comparison")} {onclick}>{t.get("Remove")}</Button>
+              </div>
+            }
+        })
+        .collect();
+
+    let table = (units.len() > 1).then(|| {
+        let headers = units.iter().map(|(_, _, unit)| {
+            html! { <th><Text text={&unit.name} /></th> }
+        });
+        let rows = STATS.iter().map(|stat| {
+            let base = (stat.value)(&units[0].2);
+            let cells = units.iter().enumerate().map(|(i, (_, _, unit))| {
+                let value = (stat.value)(unit);
+                let diff = match (i, base, value) {
+                    (0, _, _) => None,
+                    (_, Some(base), Some(value)) => Some(value as i64 - base as i64),
+                    _ => None,
+                };
+                let class = diff.and_then(|d| {
+                    match d.signum() * if stat.lower_is_better { -1 } else { 1 } {
+                        1 => Some("better"),
+                        -1 => Some("worse"),
+                        _ => None,
+                    }
+                });
+                html! {
+                  <td class={classes!(class)}>
+                    <span class="value">{ value.map_or("—".to_string(), |v| v.to_string()) }</span>
+                    if let Some(diff) = diff.filter(|d| *d != 0) {
+                      <span class="diff">{ format!("{diff:+}") }</span>
+                    }
+                  </td>
+                }
+            });
+            html! {
+              <tr>
+                <th>{ t.get(stat.name) }</th>
+                {for cells}
+              </tr>
+            }
+        });
+        html! {
+          <table class="stat-diff">
+            <thead>
+              <tr>
+                <th></th>
+                {for headers}
+              </tr>
+            </thead>
+            <tbody>
+              {for rows}
+            </tbody>
+          </table>
+        }
+    });
+
+    html! {
+    <div class="compare-page">
+      <header class="header-container">
+        <div class="nav">
+          <BackLink />
+        </div>
+        <div class="header">
+          <div class="name">{t.get("Compare units")}</div>
+          <UnitPicker {module} picks={picks.clone()} {set_picks} />
+        </div>
+      </header>
+      <main>
+        <div class="compare-columns">
+          {for columns}
+        </div>
+        {table}
+      </main>
+    </div>
+    }
+}
+
+#[autoprops]
+#[function_component(UnitPicker)]
+fn unit_picker(
+    module: Module,
+    picks: Vec<(IString, IString)>,
+    set_picks: Callback<Vec<(IString, IString)>>,
+) -> Html {
+    let t = use_messages();
+    let source = use_state(|| {
+        module
+            .factions
+            .values()
+            .find(|f| f.roster.len() > 0)
+            .map(|f| f.id.clone())
+            .unwrap_or_else(|| MERCS.into())
+    });
+
+    let on_source = {
+        let source = source.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            source.set(select.value().into());
+        })
+    };
+
+    let on_unit = {
+        let source = source.clone();
+        let picks = picks.clone();
+        let set_picks = set_picks.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let unit = select.value();
+            select.set_value("");
+            if unit.len() > 0 {
+                let mut picks = picks.clone();
+                picks.push(((*source).clone(), unit.into()));
+                set_picks.emit(picks);
+            }
+        })
+    };
+
+    let sources = module
+        .factions
+        .values()
+        .filter(|f| f.roster.len() > 0)
+        .map(|f| {
+            html! {
+              <option value={&f.id} selected={*source == f.id}>{ f.name.replace('\n', " ") }</option>
+            }
+        });
+
+    let units = source_units(&module, &source).into_iter().map(|u| {
+        html! {
+          <option value={&u.id}>{ u.name.replace('\n', " ") }</option>
+        }
+    });
+
+    html! {
+      <div class="unit-picker">
+        <select onchange={on_source}>
+          {for sources}
+          if module.pools.len() > 0 {
+            <option value={MERCS} selected={*source == MERCS}>{t.get("Mercenaries")}</option>
+          }
+        </select>
+        <select onchange={on_unit}>
+          <option value="" selected={true}>{t.get("Add a unit...")}</option>
+          {for units}
+        </select>
+      </div>
+    }
+}
@@ -4,11 +4,12 @@ use yew_autoprops::autoprops;
 use crate::{
     AppContext,
     components::{
-        BackLink, Button, Dialog, FactionRoster, HelpDialog, RegionalRoster, RosterFilter, Text,
-        UnitFilter,
+        BackLink, Button, Dialog, Explorer, FactionRoster, HelpDialog, Link, RecruitmentExplorer,
+        RegionalRoster, RosterFilter, Text, UnitFilter,
     },
-    hooks::ModelHandle,
+    hooks::{ModelHandle, use_messages, use_query_model},
     model::{Faction, Module},
+    routes::Route,
 };
 
 #[autoprops]
@@ -19,14 +20,22 @@ pub fn faction_page(module_id: AttrValue, faction_id: AttrValue) -> Html {
     let aliases = &module.aliases;
     let faction_id = aliases.get(&faction_id).unwrap_or(&faction_id);
     let faction = module.factions.get(faction_id).unwrap();
+    let t = use_messages();
 
-    let filter = use_state(|| UnitFilter {
+    let filter = use_query_model(|| UnitFilter {
         era: (faction.eras.len() > 1).then(|| faction.eras[0].clone()),
         horde: faction.is_horde.then_some(false),
         regional: (faction.aors.len() > 0).then_some(false),
+        ..Default::default()
     });
 
     let help_dialog = use_state(|| None as Option<Box<dyn Dialog>>);
+    let explorer = use_state(|| None as Option<Explorer>);
+    let explored = explorer
+        .as_ref()
+        .map(|e| e.apply(module, faction))
+        .unwrap_or_else(|| faction.clone());
+    let explored = &explored;
 
     let show_help = Callback::from({
         let help_dialog = help_dialog.clone();
@@ -35,26 +44,45 @@ pub fn faction_page(module_id: AttrValue, faction_id: AttrValue) -> Html {
         }
     });
 
+    let toggle_explorer = Callback::from({
+        let explorer = explorer.clone();
+        move |()| {
+            explorer.set(match *explorer {
+                Some(_) => None,
+                None => Some(Explorer::default()),
+            })
+        }
+    });
+
+    let army_route = Route::Army {
+        module: module.id.clone(),
+        faction: faction.id_or_alias(),
+    };
+
     html! {
     <div class="faction-page">
       <header class="header-container">
         <div class="nav">
           <BackLink />
-          // <Button>
-          //   <img class="settings button" title="Configure" src="/icons/ui/settings.webp" />
-          // </Button>
+          <Button onclick={toggle_explorer}>
+            <img class="settings button" title={t.get("Explore recruitment")} src="/icons/ui/settings.webp" />
+          </Button>
+          <Link to={army_route}>
+            <img class="army button" title={t.get("Build an army")} src="/icons/ui/army.svg" />
+          </Link>
           <Button onclick={show_help}>
-            <img class="help button" title="Help" src="/icons/ui/help.webp" />
+            <img class="help button" title={t.get("Help")} src="/icons/ui/help.webp" />
           </Button>
           <HelpDialog control={help_dialog.setter().to_callback()} />
         </div>
-        <FactionHeader class="header" {module} faction={faction.clone()} filter={&filter} />
+        <FactionHeader class="header" {module} faction={faction.clone()} filter={filter.clone()} />
       </header>
+      <RecruitmentExplorer {module} {faction} {explorer} />
       <main>
         if let Some(true) = filter.regional {
-          <RegionalRoster {faction} filter={&*filter} />
+          <RegionalRoster {module} faction={explored} filter={&*filter} />
         } else {
-          <FactionRoster roster={&faction.roster} filter={&*filter} />
+          <FactionRoster roster={&explored.roster} filter={&*filter} />
         }
       </main>
     </div>
@@ -3,7 +3,8 @@ use yew::prelude::*;
 use yew_autoprops::autoprops;
 
 use crate::{
-    components::{Icon, UnitCard, UnitFilter},
+    components::{Icon, UnitCard, UnitFilter, unit_anchor},
+    hooks::use_messages,
     model::{Unit, UnitClass},
 };
 
@@ -25,29 +26,34 @@ pub fn faction_roster(roster: IArray<Unit>, filter: UnitFilter) -> Html {
     }
 }
 
+pub fn class_title(class: UnitClass) -> &'static str {
+    match class {
+        UnitClass::Sword => "Blade infantry",
+        UnitClass::Spear => "Spear infantry",
+        UnitClass::Missile => "Missile infantry",
+        UnitClass::Cavalry => "Cavalry",
+        UnitClass::General => "General bodyguards",
+        UnitClass::Animal => "Animals",
+        UnitClass::Artillery => "Artillery",
+        UnitClass::Ship => "Navy",
+    }
+}
+
 #[autoprops]
 #[function_component(RosterGroup)]
 fn roster_group(roster: IArray<Unit>, group: UnitClass) -> Html {
+    let t = use_messages();
     let cards: Vec<_> = roster
         .iter()
         .filter(|u| u.class == group)
         .map(|unit| {
             html! {
-              <UnitCard {unit}/>
+              <UnitCard anchor={unit_anchor(&unit)} {unit}/>
             }
         })
         .collect();
 
-    let title = match group {
-        UnitClass::Sword => "Blade infantry",
-        UnitClass::Spear => "Spear infantry",
-        UnitClass::Missile => "Missile infantry",
-        UnitClass::Cavalry => "Cavalry",
-        UnitClass::General => "General bodyguards",
-        UnitClass::Animal => "Animals",
-        UnitClass::Artillery => "Artillery",
-        UnitClass::Ship => "Navy",
-    };
+    let title = t.get(class_title(group));
 
     html! {
       <>
@@ -12,6 +12,7 @@ use crate::{
         AbilitiesRow, CostRow, DefenseRow, Icon, MentalRow, PoolRow, SizeRow, TerrainRow, UnitCard,
         UpkeepRow, WeaponRow,
     },
+    hooks::use_messages,
     model::{Ability, GroundBonus, PoolEntry, Unit, Weapon},
 };
 
@@ -54,6 +55,7 @@ pub struct HelpData {
 #[autoprops]
 #[function_component(HelpDialog)]
 pub fn help_dialog(#[prop_or_default] control: Callback<Option<Box<dyn Dialog>>>) -> Html {
+    let t = use_messages();
     let popover_ref = use_node_ref();
 
     use_effect({
@@ -118,24 +120,24 @@ pub fn help_dialog(#[prop_or_default] control: Callback<Option<Box<dyn Dialog>>>
       <div ref={popover_ref} popover="">
         <div  class="help-dialog">
           <div class="descr left">
-            <button onclick={close_details} class="name">{"Name"}</button>
-            <button onclick={open_details("soldiers")} class="soldiers">{"Formation"}</button>
-            <button onclick={open_details("cost")} class="cost">{"Recruitment"}</button>
-            <button onclick={open_details("upkeep")} class="upkeep">{"Upkeep"}</button>
-            <button onclick={open_details("mental")} class="mental">{"Soldiers"}</button>
-            <button onclick={open_details("terrain")} class="terrain">{"Terrain"}</button>
-            <button onclick={open_details("weapons")} class="weapons">{"Weapons"}</button>
-            <button onclick={open_details("defenses")} class="defenses">{"Defense"}</button>
+            <button onclick={close_details} class="name">{t.get("Name")}</button>
+            <button onclick={open_details("soldiers")} class="soldiers">{t.get("Formation")}</button>
+            <button onclick={open_details("cost")} class="cost">{t.get("Recruitment")}</button>
+            <button onclick={open_details("upkeep")} class="upkeep">{t.get("Upkeep")}</button>
+            <button onclick={open_details("mental")} class="mental">{t.get("Soldiers")}</button>
+            <button onclick={open_details("terrain")} class="terrain">{t.get("Terrain")}</button>
+            <button onclick={open_details("weapons")} class="weapons">{t.get("Weapons")}</button>
+            <button onclick={open_details("defenses")} class="defenses">{t.get("Defense")}</button>
           </div>
           <UnitCard {unit} pool={&display_pool} />
           <div class="descr right">
-            <button onclick={open_details("abilities")} class="abilities">{"Abilities"}</button>
-            <button onclick={open_details("pool")} class="pool">{"Merc pool"}</button>
+            <button onclick={open_details("abilities")} class="abilities">{t.get("Abilities")}</button>
+            <button onclick={open_details("pool")} class="pool">{t.get("Merc pool")}</button>
           </div>
           <div class="descr details">
             <details ref={&refs["soldiers"]} name="help-section">
               <summary>
-                {"Formations"}
+                {t.get("Formations")}
                 <div class="unit-card">
                   <div class="frame">
                     <SizeRow class="size-row row" {unit} />
@@ -144,33 +146,36 @@ pub fn help_dialog(#[prop_or_default] control: Callback<Option<Box<dyn Dialog>>>
               </summary>
               <div class="help-table">
                 <Icon class="icon" src="/icons/stat.svg" symbol="soldiers" />
-                <span>{"unit size ("}<span class="soldiers">{"soldiers"}</span>{" and "}<span class="officers">{"officers"}</span>{")"}</span>
+                <span>{t.format_html("unit size ({} and {})", &[
+                  html! { <span class="soldiers">{t.get("soldiers")}</span> },
+                  html! { <span class="officers">{t.get("officers")}</span> },
+                ])}</span>
 
                 <Icon class="icon" src="/icons/formation.svg" symbol="square" />
-                <span>{"square"}</span>
+                <span>{t.get("square")}</span>
 
                 <Icon class="icon" src="/icons/formation.svg" symbol="horde" />
-                <span>{"horde"}</span>
+                <span>{t.get("horde")}</span>
 
                 <Icon class="icon" src="/icons/formation.svg" symbol="phalanx" />
-                <span>{"phalanx"}</span>
+                <span>{t.get("phalanx")}</span>
 
                 <Icon class="icon" src="/icons/formation.svg" symbol="testudo" />
-                <span>{"testudo"}</span>
+                <span>{t.get("testudo")}</span>
 
                 <Icon class="icon" src="/icons/formation.svg" symbol="wedge" />
-                <span>{"wedge"}</span>
+                <span>{t.get("wedge")}</span>
 
                 <Icon class="icon" src="/icons/formation.svg" symbol="schiltrom" />
-                <span>{"schiltrom"}</span>
+                <span>{t.get("schiltrom")}</span>
 
                 <Icon class="icon" src="/icons/formation.svg" symbol="shield_wall" />
-                <span>{"shield wall"}</span>
+                <span>{t.get("shield wall")}</span>
               </div>
             </details>
             <details ref={&refs["cost"]} name="help-section">
               <summary>
-                {"Recruitment"}
+                {t.get("Recruitment")}
                 <div class="unit-card">
                   <div class="frame">
                     <CostRow class="cost-row row" {unit} />
@@ -179,15 +184,15 @@ pub fn help_dialog(#[prop_or_default] control: Callback<Option<Box<dyn Dialog>>>
               </summary>
               <div class="help-table">
                 <Icon class="icon" src="/icons/stat.svg" symbol="cost" />
-                <span class="cost">{"cost to recruit"}</span>
+                <span class="cost">{t.get("cost to recruit")}</span>
 
                 <Icon class="icon" src="/icons/attribute.svg" symbol="turns" />
-                <span class="turns">{"turns to recruit"}</span>
+                <span class="turns">{t.get("turns to recruit")}</span>
               </div>
             </details>
             <details ref={&refs["upkeep"]} name="help-section">
               <summary>
-                {"Upkeep"}
+                {t.get("Upkeep")}
                 <div class="unit-card">
                   <div class="frame">
                     <UpkeepRow class="upkeep-row row" {unit} />
@@ -196,12 +201,12 @@ pub fn help_dialog(#[prop_or_default] control: Callback<Option<Box<dyn Dialog>>>
               </summary>
               <div class="help-table">
                 <Icon class="icon" src="/icons/stat.svg" symbol="upkeep" />
-                <span class="upkeep">{"upkeep cost"}</span>
+                <span class="upkeep">{t.get("upkeep cost")}</span>
               </div>
             </details>
             <details ref={&refs["mental"]} name="help-section">
               <summary>
-                {"Soldiers"}
+                {t.get("Soldiers")}
                 <div class="unit-card">
                   <div class="frame">
                     <MentalRow class="mental-row row" {unit} />
@@ -210,45 +215,47 @@ pub fn help_dialog(#[prop_or_default] control: Callback<Option<Box<dyn Dialog>>>
               </summary>
               <div class="help-table">
                 <Icon class="icon" src="/icons/discipline.svg" symbol="normal" />
-                <span>{"medium discipline ("}<span class="morale">{"morale"}</span>{")"}</span>
+                <span>{t.format_html("medium discipline ({})", &[
+                  html! { <span class="morale">{t.get("morale")}</span> },
+                ])}</span>
 
                 <Icon class="icon" src="/icons/discipline.svg" symbol="low" />
-                <span>{"low discipline (morale)"}</span>
+                <span>{t.get("low discipline (morale)")}</span>
 
                 <Icon class="icon" src="/icons/discipline.svg" symbol="disciplined" />
-                <span>{"high discipline (morale)"}</span>
+                <span>{t.get("high discipline (morale)")}</span>
 
                 <Icon class="icon" src="/icons/discipline.svg" symbol="impetuous" />
-                <span>{"impetuous (morale)"}</span>
+                <span>{t.get("impetuous (morale)")}</span>
 
                 <Icon class="icon" src="/icons/discipline.svg" symbol="berserker" />
-                <span>{"berserker (morale)"}</span>
+                <span>{t.get("berserker (morale)")}</span>
 
                 <Icon class="icon" src="/icons/speed.svg" symbol="speed-1" />
-                <span>{"slow"}</span>
+                <span>{t.get("slow")}</span>
 
                 <Icon class="icon" src="/icons/speed.svg" symbol="speed-2" />
-                <span>{"medium speed"}</span>
+                <span>{t.get("medium speed")}</span>
 
                 <Icon class="icon" src="/icons/speed.svg" symbol="speed-3" />
-                <span>{"fast"}</span>
+                <span>{t.get("fast")}</span>
 
                 <Icon class="icon" src="/icons/speed.svg" symbol="speed-4" />
-                <span>{"very fast"}</span>
+                <span>{t.get("very fast")}</span>
 
                 <Icon class="icon" src="/icons/attribute.svg" symbol="stamina" />
-                <span>{"stamina"}</span>
+                <span>{t.get("stamina")}</span>
 
                 <Icon class="icon" src="/icons/attribute.svg" symbol="inexhaustible" />
-                <span>{"inexhaustible"}</span>
+                <span>{t.get("inexhaustible")}</span>
 
                 <Icon class="icon" src="/icons/attribute.svg" symbol="heat" />
-                <span>{"heat penalty"}</span>
+                <span>{t.get("heat penalty")}</span>
               </div>
             </details>
             <details ref={&refs["terrain"]} name="help-section">
               <summary>
-                {"Terrain"}
+                {t.get("Terrain")}
                 <div class="unit-card">
                   <div class="frame">
                     <TerrainRow class="terrain row" unit={display_terrain} />
@@ -257,21 +264,21 @@ pub fn help_dialog(#[prop_or_default] control: Callback<Option<Box<dyn Dialog>>>
               </summary>
               <div class="help-table">
                 <Icon class="icon" src="/icons/terrain.svg" symbol="scrub" />
-                <span>{"scrub"}</span>
+                <span>{t.get("scrub")}</span>
 
                 <Icon class="icon" src="/icons/terrain.svg" symbol="forest" />
-                <span>{"forest"}</span>
+                <span>{t.get("forest")}</span>
 
                 <Icon class="icon" src="/icons/terrain.svg" symbol="sand" />
-                <span>{"sand"}</span>
+                <span>{t.get("sand")}</span>
 
                 <Icon class="icon" src="/icons/terrain.svg" symbol="snow" />
-                <span>{"snow"}</span>
+                <span>{t.get("snow")}</span>
               </div>
             </details>
             <details ref={&refs["weapons"]} name="help-section">
               <summary>
-                {"Weapons"}
+                {t.get("Weapons")}
                 <div class="unit-card">
                   <div class="frame">
                     <WeaponRow class="weapon1-row row" {unit} weapon={display_weapon} />
@@ -280,46 +287,48 @@ pub fn help_dialog(#[prop_or_default] control: Callback<Option<Box<dyn Dialog>>>
               </summary>
               <div class="help-table">
                 <Icon class="icon" src="/icons/weapon.svg" symbol="melee" />
-                <span>{"blade ("}<span class="strength"><strong>{"strength"}</strong></span>
-                  {" and "}<span class="lethality">{"lethality"}</span>{")"}</span>
+                <span>{t.format_html("blade ({} and {})", &[
+                  html! { <span class="strength"><strong>{t.get("strength")}</strong></span> },
+                  html! { <span class="lethality">{t.get("lethality")}</span> },
+                ])}</span>
 
                 <Icon class="icon" src="/icons/weapon.svg" symbol="spear" />
-                <span>{"spear (strength and lethality)"}</span>
+                <span>{t.get("spear (strength and lethality)")}</span>
 
                 <Icon class="icon" src="/icons/weapon.svg" symbol="missile" />
-                <span>{"missile (strength and lethality)"}</span>
+                <span>{t.get("missile (strength and lethality)")}</span>
 
                 <Icon class="icon" src="/icons/weapon.svg" symbol="thrown" />
-                <span>{"thrown (strength and lethality)"}</span>
+                <span>{t.get("thrown (strength and lethality)")}</span>
 
                 <Icon class="icon" src="/icons/weapon.svg" symbol="gunpowder" />
-                <span>{"firearm (strength and lethality)"}</span>
+                <span>{t.get("firearm (strength and lethality)")}</span>
 
                 <Icon class="icon" src="/icons/weapon.svg" symbol="cannon" />
-                <span>{"cannon (strength and lethality)"}</span>
+                <span>{t.get("cannon (strength and lethality)")}</span>
 
                 <Icon class="icon" src="/icons/attribute.svg" symbol="range" />
-                <span>{"range"}</span>
+                <span>{t.get("range")}</span>
 
                 <Icon class="icon" src="/icons/attribute.svg" symbol="ammo" />
-                <span>{"ammunition"}</span>
+                <span>{t.get("ammunition")}</span>
 
                 <Icon class="icon" src="/icons/attribute.svg" symbol="charge" />
-                <span>{"charge bonus"}</span>
+                <span>{t.get("charge bonus")}</span>
 
                 <Icon class="icon" src="/icons/attribute.svg" symbol="against-cavalry" />
-                <span>{"bonus against cavalry"}</span>
+                <span>{t.get("bonus against cavalry")}</span>
 
                 <Icon class="icon" src="/icons/attribute.svg" symbol="armor-piercing" />
-                <span>{"armor piercing"}</span>
+                <span>{t.get("armor piercing")}</span>
 
                 <Icon class="icon" src="/icons/attribute.svg" symbol="precharge" />
-                <span>{"thrown before charge"}</span>
+                <span>{t.get("thrown before charge")}</span>
               </div>
             </details>
             <details ref={&refs["defenses"]} name="help-section">
               <summary>
-                {"Defenses"}
+                {t.get("Defenses")}
                 <div class="unit-card">
                   <div class="frame">
                     <DefenseRow class="defense1-row row" def={&unit.defense} hp={display_hp} />
@@ -328,27 +337,29 @@ pub fn help_dialog(#[prop_or_default] control: Callback<Option<Box<dyn Dialog>>>
               </summary>
               <div class="help-table">
                 <Icon class="icon" src="/icons/stat.svg" symbol="defense" />
-                <span>{"soldier defenses ("}<span class="defense"><strong>{"total"}</strong></span>{")"}</span>
+                <span>{t.format_html("soldier defenses ({})", &[
+                  html! { <span class="defense"><strong>{t.get("total")}</strong></span> },
+                ])}</span>
 
                 <Icon class="icon" src="/icons/stat.svg" symbol="defense-mount" />
-                <span>{"mount defenses (total)"}</span>
+                <span>{t.get("mount defenses (total)")}</span>
 
                 <Icon class="icon" src="/icons/attribute.svg" symbol="armor" />
-                <span>{"armor"}</span>
+                <span>{t.get("armor")}</span>
 
                 <Icon class="icon" src="/icons/attribute.svg" symbol="skill" />
-                <span>{"skill"}</span>
+                <span>{t.get("skill")}</span>
 
                 <Icon class="icon" src="/icons/attribute.svg" symbol="shield" />
-                <span>{"shield"}</span>
+                <span>{t.get("shield")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="heart" />
-                <span>{"hit points"}</span>
+                <span>{t.get("hit points")}</span>
               </div>
             </details>
             <details ref={&refs["abilities"]} name="help-section">
               <summary>
-                {"Abilities"}
+                {t.get("Abilities")}
                 <div class="unit-card">
                   <div class="frame">
                     <AbilitiesRow class="abilities row" unit={display_abilities} />
@@ -357,70 +368,75 @@ pub fn help_dialog(#[prop_or_default] control: Callback<Option<Box<dyn Dialog>>>
               </summary>
               <div class="help-table">
                 <Icon class="icon" src="/icons/ability.svg" symbol="cantabrian-circle" />
-                <span>{"Cantabrian circle"}</span>
+                <span>{t.get("Cantabrian circle")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="power-charge" />
-                <span>{"powerful charge"}</span>
+                <span>{t.get("powerful charge")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="formed-charge" />
-                <span>{"formed charge"}</span>
+                <span>{t.get("formed charge")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="warcry" />
-                <span>{"warcry"}</span>
+                <span>{t.get("warcry")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="chant" />
-                <span>{"chanting/screeching"}</span>
+                <span>{t.get("chanting/screeching")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="command" />
-                <span>{"inspires units"}</span>
+                <span>{t.get("inspires units")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="frighten-all" />
-                <span>{"frighten all units"}</span>
+                <span>{t.get("frighten all units")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="frighten-foot" />
-                <span>{"frighten infantry"}</span>
+                <span>{t.get("frighten infantry")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="frighten-mounted" />
-                <span>{"frighten cavalry"}</span>
+                <span>{t.get("frighten cavalry")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="can-run-amok" />
-                <span>{"may run amok"}</span>
+                <span>{t.get("may run amok")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="cant-hide" />
-                <span>{"cannot hide"}</span>
+                <span>{t.get("cannot hide")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="hide-anywhere" />
-                <span>{"hide anywhere"}</span>
+                <span>{t.get("hide anywhere")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="hide-forest" />
-                <span>{"hide well in forests"}</span>
+                <span>{t.get("hide well in forests")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="hide-grass" />
-                <span>{"hide in long grass"}</span>
+                <span>{t.get("hide in long grass")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="stakes" />
-                <span>{"defensive stakes"}</span>
+                <span>{t.get("defensive stakes")}</span>
 
                 <Icon class="icon" src="/icons/ability.svg" symbol="knight" />
-                <span>{"knight"}</span>
+                <span>{t.get("knight")}</span>
               </div>
             </details>
             <details ref={&refs["pool"]} name="help-section">
               <summary>
-                {"Merc pool"}
+                {t.get("Merc pool")}
                 <div class="unit-card">
                   <PoolRow class="pool row" pool={display_pool} />
                 </div>
               </summary>
               <div class="help-table">
                 <Icon class="icon" src="/icons/attribute.svg" symbol="turns" />
-                <span><span class="replenish"><strong>{"turns"}</strong></span>{" to replenish ("}<span class="interval">{"95% interval"}</span>{")"}</span>
+                <span>{t.format_html("{} to replenish ({})", &[
+                  html! { <span class="replenish"><strong>{t.get("turns")}</strong></span> },
+                  html! { <span class="interval">{t.get("95% interval")}</span> },
+                ])}</span>
 
                 <span class="cross">{"×"}</span>
-                <span><span class="max"><strong>{"max"}</strong></span>{" pool size"}</span>
+                <span>{t.format_html("{} pool size", &[
+                  html! { <span class="max"><strong>{t.get("max")}</strong></span> },
+                ])}</span>
 
                 <Icon class="icon" src="/icons/exp.svg" symbol="exp-blank" />
-                <span>{"experience (0-9)"}</span>
+                <span>{t.get("experience (0-9)")}</span>
 
                 // TODO restricts
                 // <Icon class="icon" src="/icons/exp.svg" symbol="exp-3" />
@@ -0,0 +1,126 @@
+use implicit_clone::unsync::IString;
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+
+use crate::{
+    AppContext,
+    components::{BackLink, Link, RegionMap, Text},
+    hooks::use_messages,
+    model::{Module, Region},
+    routes::Route,
+};
+
+#[autoprops]
+#[function_component(MapPage)]
+pub fn map_page(module_id: AttrValue) -> Html {
+    let ctx = use_context::<AppContext>().expect("no context");
+    let module = &ctx.modules[&module_id];
+    let t = use_messages();
+
+    let selected = use_state(|| None as Option<IString>);
+    let onclick = {
+        let selected = selected.clone();
+        Callback::from(move |id: IString| selected.set(Some(id)))
+    };
+
+    let highlight: Vec<_> = selected.iter().cloned().collect();
+    let region = selected
+        .as_ref()
+        .and_then(|id| module.regions.iter().find(|r| &r.id == id));
+
+    html! {
+    <div class="map-page">
+      <header class="header-container">
+        <div class="nav">
+          <BackLink />
+        </div>
+        <div class="header">
+          <div class="name">{t.get("Campaign map")}</div>
+        </div>
+      </header>
+      <main>
+        <RegionMap {module} {highlight} {onclick} />
+        if let Some(region) = region {
+          <RegionPanel {module} {region} />
+        } else {
+          <p class="hint">{t.get("Click a region to see what can be recruited there.")}</p>
+        }
+      </main>
+    </div>
+    }
+}
+
+#[autoprops]
+#[function_component(RegionPanel)]
+fn region_panel(module: Module, region: Region) -> Html {
+    let t = use_messages();
+    let factions = region.units.iter().filter_map(|(id, units)| {
+        let faction = module.factions.get(id)?;
+        let units = units.iter().filter_map(|id| {
+            let unit = faction.roster.iter().find(|u| u.id == id)?;
+            let to = Route::Unit {
+                module: module.id.clone(),
+                faction: faction.id_or_alias(),
+                unit: unit.key.clone(),
+            };
+            Some(html! {
+              <li><Link {to}><Text text={&unit.name} /></Link></li>
+            })
+        });
+        Some(html! {
+          <div class="faction">
+            <div class="title">
+              <img class="icon" src={&faction.image} />
+              <span class="name">{ &faction.name }</span>
+            </div>
+            <ul class="units">
+              {for units}
+            </ul>
+          </div>
+        })
+    });
+
+    let pools = region.pools.iter().filter_map(|id| {
+        let pool = module.pools.iter().find(|p| p.id == id)?;
+        let to = Route::Mercenaries {
+            module: module.id.clone(),
+        };
+        Some(html! {
+          <li><Link {to}>{ &pool.name }</Link></li>
+        })
+    });
+    let resources = region
+        .hidden_resources
+        .iter()
+        .map(|r| html! { <li>{ r }</li> });
+
+    html! {
+      <div class="region-panel">
+        <h2>{ &region.name }</h2>
+        <div class="details">
+          <span class="settlement">{ &region.settlement }</span>
+          <span class="id">{ &region.id }</span>
+        </div>
+        if !region.hidden_resources.is_empty() {
+          <h3>{t.get("Hidden resources")}</h3>
+          <ul class="resources">
+            {for resources}
+          </ul>
+        }
+        if !region.pools.is_empty() {
+          <h3>{t.get("Mercenary pools")}</h3>
+          <ul class="pools">
+            {for pools}
+          </ul>
+        }
+        <h3>{t.get("Recruitment")}</h3>
+        if region.units.is_empty() {
+          <p>{t.get("No faction can recruit here.")}</p>
+        } else {
+          <div class="factions">
+            {for factions}
+          </div>
+        }
+      </div>
+    }
+}
@@ -4,7 +4,7 @@ use yew_autoprops::autoprops;
 use crate::{
     AppContext,
     components::{BackLink, Button, Dialog, HelpDialog, MercenaryRoster, RosterFilter, UnitFilter},
-    hooks::ModelHandle,
+    hooks::{ModelHandle, use_messages, use_query_model},
     model::Module,
 };
 
@@ -13,8 +13,9 @@ use crate::{
 pub fn mercenary_page(module_id: AttrValue) -> Html {
     let ctx = use_context::<AppContext>().expect("no context");
     let module = &ctx.modules[&module_id];
+    let t = use_messages();
 
-    let filter = use_state(|| UnitFilter::default());
+    let filter = use_query_model(UnitFilter::default);
 
     // TODO refactor this out in common with FactionPage
     let help_dialog = use_state(|| None as Option<Box<dyn Dialog>>);
@@ -35,14 +36,14 @@ pub fn mercenary_page(module_id: AttrValue) -> Html {
           //   <img class="settings button" title="Configure" src="/icons/ui/settings.webp" />
           // </Button>
           <Button onclick={show_help}>
-            <img class="help button" title="Help" src="/icons/ui/help.webp" />
+            <img class="help button" title={t.get("Help")} src="/icons/ui/help.webp" />
           </Button>
           <HelpDialog control={help_dialog.setter().to_callback()} />
         </div>
-        <MercenaryHeader class="header" {module} filter={&filter} />
+        <MercenaryHeader class="header" {module} filter={filter.clone()} />
       </header>
       <main>
-        <MercenaryRoster pools={&module.pools} filter={&*filter} />
+        <MercenaryRoster {module} filter={&*filter} />
       </main>
     </div>
     }
@@ -51,10 +52,11 @@ pub fn mercenary_page(module_id: AttrValue) -> Html {
 #[autoprops]
 #[function_component(MercenaryHeader)]
 fn mercenary_header(class: Classes, module: Module, filter: ModelHandle<UnitFilter>) -> Html {
+    let t = use_messages();
     html! {
       <div class={classes!("faction-header", class)}>
         <div class="title">
-          <div class="name">{"Mercenaries"}</div>
+          <div class="name">{t.get("Mercenaries")}</div>
           <RosterFilter {module} {filter} />
         </div>
         <img class="icon" src="/icons/ui/mercs.webp" />
@@ -1,16 +1,19 @@
-use implicit_clone::unsync::IArray;
 use yew::prelude::*;
 use yew_autoprops::autoprops;
 
-use crate::{components::{UnitCard, UnitFilter}, model::Pool};
+use crate::{
+    components::{RegionMap, UnitCard, UnitFilter, pool_unit_anchor},
+    model::{Module, Pool},
+};
 
 #[autoprops]
 #[function_component(MercenaryRoster)]
-pub fn mercenary_roster(pools: IArray<Pool>, filter: UnitFilter) -> Html {
+pub fn mercenary_roster(module: Module, filter: UnitFilter) -> Html {
     let filter = &filter;
-    let pools = pools.into_iter().map(|pool| {
+    let module = &module;
+    let pools = module.pools.iter().map(|pool| {
         html! {
-          <MercenaryPool {pool} {filter} />
+          <MercenaryPool {module} {pool} {filter} />
         }
     });
 
@@ -23,13 +26,14 @@ pub fn mercenary_roster(pools: IArray<Pool>, filter: UnitFilter) -> Html {
 
 #[autoprops]
 #[function_component(MercenaryPool)]
-pub fn mercenary_pool(pool: Pool, filter: UnitFilter) -> Html {
-    let _ = filter; // TODO
-    let cards: Vec<_> = pool.units
+pub fn mercenary_pool(module: Module, pool: Pool, filter: UnitFilter) -> Html {
+    let cards: Vec<_> = pool
+        .units
         .iter()
+        .filter(|u| filter.apply(&u.unit))
         .map(|u| {
             html! {
-              <UnitCard unit={&u.unit} pool={u}/>
+              <UnitCard anchor={pool_unit_anchor(&pool, &u.unit)} unit={&u.unit} pool={u}/>
             }
         })
         .collect();
@@ -42,7 +46,7 @@ pub fn mercenary_pool(pool: Pool, filter: UnitFilter) -> Html {
               if pool.name.len() > 0 {
                 <span class="name">{pool.name}</span>
               }
-              <img src={pool.map} />
+              <RegionMap {module} highlight={&pool.regions} />
             </div>
             <div class="unit-cards">
               {for cards}
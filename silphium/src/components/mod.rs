@@ -1,26 +1,42 @@
 mod core;
 pub use core::*;
 
+mod army_page;
+mod buildings_page;
+mod compare_page;
 mod faction_page;
 mod faction_roster;
 mod help_dialog;
+mod map_page;
 mod mercenary_page;
 mod mercenary_roster;
 mod module_list;
 mod module_page;
+mod recruitment_explorer;
+mod region_map;
 mod regional_roster;
 mod roster_filter;
 mod unit_card;
 mod unit_filter;
+mod unit_page;
+mod unit_search;
 
+pub use army_page::*;
+pub use buildings_page::*;
+pub use compare_page::*;
 pub use faction_page::*;
 pub use faction_roster::*;
 pub use help_dialog::*;
+pub use map_page::*;
 pub use mercenary_page::*;
 pub use mercenary_roster::*;
 pub use module_list::*;
 pub use module_page::*;
+pub use recruitment_explorer::*;
+pub use region_map::*;
 pub use regional_roster::*;
 pub use roster_filter::*;
 pub use unit_card::*;
 pub use unit_filter::*;
+pub use unit_page::*;
+pub use unit_search::*;
@@ -30,7 +30,8 @@ fn module_link(m: Module) -> Html {
     let route = Route::Module { module: m.id };
     html! {
       <Link<Route> to={route}>
-        <img class="logo" src={ m.banner } title={ m.name } />
+        <img class="logo" src={ m.banner } title={ &m.name } />
+        <div class="name">{ m.name }</div>
       </Link<Route>>
     }
 }
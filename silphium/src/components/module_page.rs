@@ -1,6 +1,7 @@
 use crate::{
     AppContext,
-    components::{BackLink, Link, Text},
+    components::{BackLink, Link, Text, UnitSearch},
+    hooks::use_messages,
     model::{Faction, Module},
     routes::Route,
 };
@@ -14,6 +15,7 @@ use yew_router::hooks::use_route;
 pub fn module_page(id: IString) -> Html {
     let ctx = use_context::<AppContext>().expect("no context");
     let module = &ctx.modules[&id];
+    let t = use_messages();
 
     let links = module
         .factions
@@ -23,6 +25,15 @@ pub fn module_page(id: IString) -> Html {
     let merc_route = Route::Mercenaries {
         module: module.id.clone(),
     };
+    let compare_route = Route::Compare {
+        module: module.id.clone(),
+    };
+    let buildings_route = Route::Buildings {
+        module: module.id.clone(),
+    };
+    let map_route = Route::Map {
+        module: module.id.clone(),
+    };
 
     html! {
       <div class="module-page">
@@ -32,12 +43,29 @@ pub fn module_page(id: IString) -> Html {
           }
           <ModuleHeader {module} />
         </header>
+        <UnitSearch {module} />
         <main>
           {for links}
         if module.pools.len() > 0 {
           <Link to={merc_route}>
-            <img class="icon" src="/icons/ui/mercs.webp" title="Mercenaries" />
-            <div class="name">{"Mercenaries"}</div>
+            <img class="icon" src="/icons/ui/mercs.webp" title={t.get("Mercenaries")} />
+            <div class="name">{t.get("Mercenaries")}</div>
+          </Link>
+        }
+          <Link to={compare_route}>
+            <img class="icon" src="/icons/ui/compare.svg" title={t.get("Compare units")} />
+            <div class="name">{t.get("Compare units")}</div>
+          </Link>
+        if !module.buildings.is_empty() {
+          <Link to={buildings_route}>
+            <img class="icon" src="/icons/ui/buildings.svg" title={t.get("Buildings")} />
+            <div class="name">{t.get("Buildings")}</div>
+          </Link>
+        }
+        if module.map.is_some() {
+          <Link to={map_route}>
+            <img class="icon" src="/icons/ui/map.svg" title={t.get("Campaign map")} />
+            <div class="name">{t.get("Campaign map")}</div>
           </Link>
         }
         </main>
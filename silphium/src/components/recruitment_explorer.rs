@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+
+use implicit_clone::{ImplicitClone, unsync::IString};
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+
+use crate::{
+    eval::{Choices, Conditions, Evaluator, evaluate},
+    hooks::{ModelHandle, use_messages},
+    model::{Aor, Faction, Module},
+};
+
+/// The choices made in the recruitment explorer. Conditions without a choice
+/// stay unknown and do not hide any unit.
+#[derive(PartialEq, Clone, ImplicitClone, Default)]
+pub struct Explorer {
+    pub region: Option<IString>,
+    pub events: HashMap<IString, bool>,
+    pub resources: HashMap<IString, bool>,
+    pub buildings: HashMap<IString, Option<u32>>,
+    pub port: Option<bool>,
+}
+
+impl Explorer {
+    pub fn evaluator(&self, module: &Module, faction: &Faction) -> Evaluator {
+        let region = self
+            .region
+            .as_ref()
+            .and_then(|id| module.regions.iter().find(|r| &r.id == id));
+        let hidden_resource = match region {
+            Some(region) => Some(Choices {
+                map: region.hidden_resources.iter().map(|r| (r, true)).collect(),
+                default: Some(false),
+            }),
+            None => Some(Choices {
+                map: self.resources.clone(),
+                default: None,
+            }),
+        };
+        Evaluator {
+            hidden_resource,
+            major_event: Some(Choices {
+                map: self.events.clone(),
+                default: None,
+            }),
+            building: self.buildings.clone(),
+            port: self.port,
+            ..Evaluator::faction(faction)
+        }
+    }
+
+    /// The faction with its roster and areas of recruitment reduced to the
+    /// units these choices allow.
+    pub fn apply(&self, module: &Module, faction: &Faction) -> Faction {
+        let eval = self.evaluator(module, faction);
+        let allowed = |id: &IString| {
+            module
+                .requires
+                .get(id)
+                .is_none_or(|req| evaluate(req, &module.require_aliases, &eval))
+        };
+        Faction {
+            roster: faction.roster.iter().filter(|u| allowed(&u.id)).collect(),
+            aors: faction
+                .aors
+                .iter()
+                .map(|aor| Aor {
+                    units: aor.units.iter().filter(|u| allowed(u)).collect(),
+                    ..aor
+                })
+                .collect(),
+            ..faction.clone()
+        }
+    }
+}
+
+fn on_select<F>(explorer: &ModelHandle<Option<Explorer>>, update: F) -> Callback<Event>
+where
+    F: Fn(&mut Explorer, String) + 'static,
+{
+    let explorer = explorer.clone();
+    Callback::from(move |e: Event| {
+        let select: HtmlSelectElement = e.target_unchecked_into();
+        let mut value = explorer.as_ref().cloned().unwrap_or_default();
+        update(&mut value, select.value());
+        explorer.set(Some(value));
+    })
+}
+
+fn set_choice(map: &mut HashMap<IString, bool>, key: &IString, value: &str) {
+    match value {
+        "yes" => map.insert(key.clone(), true),
+        "no" => map.insert(key.clone(), false),
+        _ => map.remove(key),
+    };
+}
+
+#[autoprops]
+#[function_component(RecruitmentExplorer)]
+pub fn recruitment_explorer(
+    module: Module,
+    faction: Faction,
+    explorer: ModelHandle<Option<Explorer>>,
+) -> Html {
+    let t = use_messages();
+    let Some(ref state) = *explorer else {
+        return html! {};
+    };
+
+    let mut conditions = Conditions::default();
+    for unit in faction.roster.iter() {
+        if let Some(req) = module.requires.get(&unit.id) {
+            conditions.collect(req, &module.require_aliases);
+        }
+    }
+
+    let tri_state_options = |value: Option<bool>| {
+        html! {
+          <>
+            <option value="" selected={value.is_none()}>{t.get("any")}</option>
+            <option value="yes" selected={value == Some(true)}>{t.get("yes")}</option>
+            <option value="no" selected={value == Some(false)}>{t.get("no")}</option>
+          </>
+        }
+    };
+
+    let mut regions: Vec<_> = module.regions.iter().collect();
+    regions.sort_by(|a, b| a.name.cmp(&b.name));
+    let regions = regions.into_iter().map(|r| {
+        let selected = state.region.as_ref() == Some(&r.id);
+        html! { <option value={&r.id} {selected}>{ &r.name }</option> }
+    });
+    let on_region = on_select(&explorer, |state, value| {
+        state.region = (!value.is_empty()).then(|| value.into());
+    });
+
+    let events = conditions.major_events.iter().map(|event| {
+        let value = state.events.get(event).copied();
+        let onchange = on_select(&explorer, {
+            let event = event.clone();
+            move |state, value| set_choice(&mut state.events, &event, &value)
+        });
+        html! {
+          <label>
+            <span class="name">{ event }</span>
+            <select {onchange}>{tri_state_options(value)}</select>
+          </label>
+        }
+    });
+
+    let resources = conditions.hidden_resources.iter().map(|resource| {
+        let value = state.resources.get(resource).copied();
+        let onchange = on_select(&explorer, {
+            let resource = resource.clone();
+            move |state, value| set_choice(&mut state.resources, &resource, &value)
+        });
+        html! {
+          <label>
+            <span class="name">{ resource }</span>
+            <select {onchange}>{tri_state_options(value)}</select>
+          </label>
+        }
+    });
+
+    let buildings = conditions.buildings.iter().map(|chain| {
+        let value = state.buildings.get(chain).copied();
+        let onchange = on_select(&explorer, {
+            let chain = chain.clone();
+            move |state, value| {
+                match value.as_str() {
+                    "" => state.buildings.remove(&chain),
+                    "none" => state.buildings.insert(chain.clone(), None),
+                    level => state.buildings.insert(chain.clone(), level.parse().ok()),
+                };
+            }
+        });
+        let levels = module
+            .buildings
+            .iter()
+            .find(|c| &c.id == chain)
+            .map(|c| c.levels.iter().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let levels = levels.into_iter().enumerate().map(|(i, level)| {
+            let selected = value == Some(Some(i as u32));
+            html! { <option value={i.to_string()} {selected}>{ &level.name }</option> }
+        });
+        html! {
+          <label>
+            <span class="name">{ chain }</span>
+            <select {onchange}>
+              <option value="" selected={value.is_none()}>{t.get("any")}</option>
+              <option value="none" selected={value == Some(None)}>{t.get("none")}</option>
+              {for levels}
+            </select>
+          </label>
+        }
+    });
+
+    let on_port = on_select(&explorer, |state, value| {
+        state.port = match value.as_str() {
+            "yes" => Some(true),
+            "no" => Some(false),
+            _ => None,
+        };
+    });
+
+    let on_reset = {
+        let explorer = explorer.clone();
+        Callback::from(move |_: MouseEvent| explorer.set(Some(Explorer::default())))
+    };
+
+    html! {
+      <div class="recruitment-explorer">
+        <div class="conditions">
+          if !module.regions.is_empty() {
+            <fieldset>
+              <legend>{t.get("Region")}</legend>
+              <select onchange={on_region}>
+                <option value="" selected={state.region.is_none()}>{t.get("any region")}</option>
+                {for regions}
+              </select>
+            </fieldset>
+          }
+          if !conditions.major_events.is_empty() {
+            <fieldset>
+              <legend>{t.get("Events")}</legend>
+              {for events}
+            </fieldset>
+          }
+          if state.region.is_none() && !conditions.hidden_resources.is_empty() {
+            <fieldset>
+              <legend>{t.get("Hidden resources")}</legend>
+              {for resources}
+            </fieldset>
+          }
+          if !conditions.buildings.is_empty() {
+            <fieldset>
+              <legend>{t.get("Buildings")}</legend>
+              {for buildings}
+            </fieldset>
+          }
+          if conditions.port {
+            <fieldset>
+              <legend>{t.get("Settlement")}</legend>
+              <label>
+                <span class="name">{t.get("port")}</span>
+                <select onchange={on_port}>{tri_state_options(state.port)}</select>
+              </label>
+            </fieldset>
+          }
+        </div>
+        <button class="reset" onclick={on_reset}>{t.get("Reset")}</button>
+      </div>
+    }
+}
@@ -0,0 +1,47 @@
+use implicit_clone::unsync::{IArray, IString};
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+
+use crate::model::Module;
+
+/// The campaign map with a set of regions highlighted. When `onclick` is
+/// given, every region can be clicked and reports its id.
+#[autoprops]
+#[function_component(RegionMap)]
+pub fn region_map(
+    module: Module,
+    #[prop_or_default] highlight: IArray<IString>,
+    #[prop_or_default] onclick: Option<Callback<IString>>,
+) -> Html {
+    let Some(ref map) = module.map else {
+        return html! {};
+    };
+
+    let regions = module
+        .regions
+        .iter()
+        .filter(|r| !r.outline.is_empty())
+        .filter(|r| onclick.is_some() || highlight.contains(&r.id))
+        .map(|r| {
+            let class = classes!("region", highlight.contains(&r.id).then_some("highlight"));
+            let onclick = onclick.as_ref().map(|onclick| {
+                let id = r.id.clone();
+                onclick.reform(move |_: MouseEvent| id.clone())
+            });
+            html! {
+              <path {class} d={&r.outline} {onclick}>
+                <title>{ &r.name }</title>
+              </path>
+            }
+        });
+
+    let class = classes!("region-map", onclick.is_some().then_some("clickable"));
+    html! {
+      <div {class}>
+        <img src={&map.image} />
+        <svg viewBox={format!("0 0 {} {}", map.width, map.height)} preserveAspectRatio="none">
+          {for regions}
+        </svg>
+      </div>
+    }
+}
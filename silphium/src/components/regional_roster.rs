@@ -4,18 +4,19 @@ use yew::prelude::*;
 use yew_autoprops::autoprops;
 
 use crate::{
-    components::{UnitCard, UnitFilter},
-    model::{Aor, Faction},
+    components::{RegionMap, UnitCard, UnitFilter},
+    model::{Aor, Faction, Module},
 };
 
 #[autoprops]
 #[function_component(RegionalRoster)]
-pub fn regional_roster(faction: Faction, filter: UnitFilter) -> Html {
+pub fn regional_roster(module: Module, faction: Faction, filter: UnitFilter) -> Html {
     let filter = &filter;
+    let module = &module;
     let faction = &faction;
     let aors = faction.aors.into_iter().map(|aor| {
         html! {
-          <AreaOfRecruitment {faction} {aor} {filter} />
+          <AreaOfRecruitment {module} {faction} {aor} {filter} />
         }
     });
 
@@ -28,12 +29,17 @@ pub fn regional_roster(faction: Faction, filter: UnitFilter) -> Html {
 
 #[autoprops]
 #[function_component(AreaOfRecruitment)]
-pub fn area_of_recruitment(faction: Faction, aor: Aor, filter: UnitFilter) -> Html {
-    let _ = filter; // TODO
+pub fn area_of_recruitment(module: Module, faction: Faction, aor: Aor, filter: UnitFilter) -> Html {
+    // every unit in an area of recruitment is regional
+    let filter = UnitFilter {
+        regional: None,
+        ..filter
+    };
     let units: HashMap<_, _> = faction.roster.iter().map(|u| (u.id.clone(), u)).collect();
     let cards: Vec<_> = aor
         .units
         .iter()
+        .filter(|u| filter.apply(&units[u]))
         .map(|u| {
             html! {
               <UnitCard unit={&units[&u]} />
@@ -49,7 +55,10 @@ pub fn area_of_recruitment(faction: Faction, aor: Aor, filter: UnitFilter) -> Ht
               if aor.name.len() > 0 {
                 <span class="name">{aor.name}</span>
               }
-              <img src={aor.map} />
+              if !aor.religions.is_empty() {
+                <span class="religions">{ aor.religions.iter().collect::<Vec<_>>().join(", ") }</span>
+              }
+              <RegionMap {module} highlight={&aor.regions} />
             </div>
             <div class="unit-cards">
               {for cards}
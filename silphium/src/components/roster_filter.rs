@@ -1,12 +1,18 @@
-use web_sys::HtmlImageElement;
+use std::{fmt::Display, str::FromStr};
+
+use web_sys::{HtmlImageElement, HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 use yew_autoprops::autoprops;
 use yew_hooks::prelude::*;
 
 use crate::{
-    components::{Icon, OptionButton, OptionGroup, Text, ToggleButton, UnitFilter},
-    hooks::ModelHandle,
-    model::{Faction, Module},
+    Messages,
+    components::{
+        Button, Icon, OptionButton, OptionGroup, Text, ToggleButton, UnitFilter, ability_title,
+        class_title,
+    },
+    hooks::{ModelHandle, use_messages},
+    model::{Ability, Faction, Module, MountType, UnitClass, WeaponType},
 };
 
 #[autoprops]
@@ -16,6 +22,7 @@ pub fn roster_filter(
     #[prop_or_default] faction: Option<Faction>,
     filter: ModelHandle<UnitFilter>,
 ) -> Html {
+    let t = use_messages();
     let horde = filter.horde_handle();
     let horde = (*horde).map(|_| horde.map(|h| h.unwrap(), Option::Some));
 
@@ -42,6 +49,7 @@ pub fn roster_filter(
         }
     });
 
+    let refine = filter.clone();
     let era_options = faction
         .map_or(Default::default(), |f| f.eras)
         .iter()
@@ -57,6 +65,7 @@ pub fn roster_filter(
         });
 
     html! {
+      <>
       <div class="eras">
         if let Some(era) = era {
           <OptionGroup class="eras" name="era" value={era}>
@@ -67,10 +76,10 @@ pub fn roster_filter(
           <div class="eras">
             <ToggleButton value={&horde}
                 class={classes!("era", horde.then_some("checked"))}
-                title={if *horde { "Show settled units" } else { "Show horde units" }}
+                title={t.get(if *horde { "Show settled units" } else { "Show horde units" })}
             >
               <Icon src="/icons/ui/horde.svg" symbol={if *horde { "on" } else { "off" }} />
-              <span>{if *horde { "Horde" } else { "Settled" }}</span>
+              <span>{t.get(if *horde { "Horde" } else { "Settled" })}</span>
             </ToggleButton>
           </div>
         }
@@ -78,14 +87,142 @@ pub fn roster_filter(
           <div class="eras">
             <ToggleButton value={&regional}
                 class={classes!("era", regional.then_some("checked"))}
-                title={if *regional { "Show global units" } else { "Show regional units" }}
+                title={t.get(if *regional { "Show global units" } else { "Show regional units" })}
             >
               <Icon src="/icons/ui/regional.svg" symbol={if *regional { "on" } else { "off" }} />
-              <span>{if *regional { "Regional" } else { "Global" }}</span>
+              <span>{t.get(if *regional { "Regional" } else { "Global" })}</span>
             </ToggleButton>
           </div>
         }
       </div>
+      <RefineFilter filter={refine} />
+      </>
+    }
+}
+
+#[autoprops]
+#[function_component(RefineFilter)]
+fn refine_filter(filter: ModelHandle<UnitFilter>) -> Html {
+    let t = &use_messages();
+    let f = &*filter;
+    let classes = UnitClass::all().map(|c| (c, class_title(c)));
+    let mounts = MountType::all().map(|m| {
+        (
+            m,
+            match m {
+                MountType::Foot => "Foot",
+                MountType::Horse => "Horse",
+                MountType::Camel => "Camel",
+                MountType::Elephant => "Elephant",
+                MountType::Chariot => "Chariot",
+                MountType::Other => "Other",
+            },
+        )
+    });
+    let abilities = Ability::all().map(|a| (a, ability_title(a)));
+    let weapons = WeaponType::all().map(|w| {
+        (
+            w,
+            match w {
+                WeaponType::Melee => "Melee weapon",
+                WeaponType::Spear => "Spear",
+                WeaponType::Missile => "Missile weapon",
+                WeaponType::Gunpowder => "Gunpowder weapon",
+                WeaponType::Thrown => "Thrown weapon",
+            },
+        )
+    });
+    let armor_piercing = [(true, "Armor piercing"), (false, "Not armor piercing")];
+
+    html! {
+      <details class="refine" open={f.is_refined()}>
+        <summary>{t.get("More filters")}</summary>
+        <div class="refine-options">
+          {enum_select(&filter, t, "Class", f.class, classes, |f, class| UnitFilter { class, ..f })}
+          {enum_select(&filter, t, "Mount", f.mount, mounts, |f, mount| UnitFilter { mount, ..f })}
+          {enum_select(&filter, t, "Ability", f.ability, abilities, |f, ability| UnitFilter { ability, ..f })}
+          {enum_select(&filter, t, "Weapon", f.weapon, weapons, |f, weapon| UnitFilter { weapon, ..f })}
+          {enum_select(&filter, t, "Armor piercing", f.armor_piercing, armor_piercing, |f, armor_piercing| UnitFilter { armor_piercing, ..f })}
+          {range_input(&filter, t, "Cost", f.min_cost, f.max_cost, |f, min_cost, max_cost| UnitFilter { min_cost, max_cost, ..f })}
+          {range_input(&filter, t, "Morale", f.min_morale, f.max_morale, |f, min_morale, max_morale| UnitFilter { min_morale, max_morale, ..f })}
+          {range_input(&filter, t, "Armor", f.min_armor, f.max_armor, |f, min_armor, max_armor| UnitFilter { min_armor, max_armor, ..f })}
+          <Button class="clear" title={t.get("Clear filters")} onclick={filter.reduce_callback(UnitFilter::cleared)}>
+            {t.get("Clear")}
+          </Button>
+        </div>
+      </details>
+    }
+}
+
+fn enum_select<T>(
+    filter: &ModelHandle<UnitFilter>,
+    t: &Messages,
+    label: &'static str,
+    value: Option<T>,
+    options: impl IntoIterator<Item = (T, &'static str)>,
+    update: fn(UnitFilter, Option<T>) -> UnitFilter,
+) -> Html
+where
+    T: Display + FromStr + PartialEq + Copy + 'static,
+{
+    let onchange = {
+        let filter = filter.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            filter.set(update((*filter).clone(), select.value().parse().ok()));
+        })
+    };
+    let options = options.into_iter().map(|(v, title)| {
+        html! {
+          <option value={v.to_string()} selected={value == Some(v)}>{t.get(title)}</option>
+        }
+    });
+
+    html! {
+      <label class="refine-option">
+        <span>{t.get(label)}</span>
+        <select {onchange}>
+          <option value="" selected={value.is_none()}>{t.get("Any")}</option>
+          {for options}
+        </select>
+      </label>
+    }
+}
+
+fn range_input(
+    filter: &ModelHandle<UnitFilter>,
+    t: &Messages,
+    label: &'static str,
+    min: Option<u32>,
+    max: Option<u32>,
+    update: fn(UnitFilter, Option<u32>, Option<u32>) -> UnitFilter,
+) -> Html {
+    let on_min = {
+        let filter = filter.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            filter.set(update((*filter).clone(), input.value().parse().ok(), max));
+        })
+    };
+    let on_max = {
+        let filter = filter.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            filter.set(update((*filter).clone(), min, input.value().parse().ok()));
+        })
+    };
+
+    html! {
+      <label class="refine-option">
+        <span>{t.get(label)}</span>
+        <div class="range">
+          <input type="number" min="0" placeholder={t.get("min")}
+              value={min.map(|m| m.to_string()).unwrap_or_default()} onchange={on_min} />
+          <span>{"–"}</span>
+          <input type="number" min="0" placeholder={t.get("max")}
+              value={max.map(|m| m.to_string()).unwrap_or_default()} onchange={on_max} />
+        </div>
+      </label>
     }
 }
 
@@ -2,27 +2,41 @@ use std::cmp::max;
 
 use yew::prelude::*;
 use yew_autoprops::autoprops;
+use yew_router::hooks::use_route;
 
 use crate::{
-    components::{Icon, Text},
+    components::{Icon, Link, Text},
+    hooks::use_messages,
     model::{
         Ability, Defense, Discipline, Formation, MountType, PoolEntry, Unit, UnitClass, Weapon,
         WeaponType,
     },
+    routes::Route,
 };
 
-fn pluralize<'a>(value: u32, singular: &'a str, plural: &'a str) -> &'a str {
+fn pluralize(value: u32, singular: &'static str, plural: &'static str) -> &'static str {
     if value == 1 { singular } else { plural }
 }
 
 #[autoprops]
 #[function_component(UnitCard)]
-pub fn unit_card(unit: Unit, #[prop_or_default] pool: Option<PoolEntry>) -> Html {
+pub fn unit_card(
+    unit: Unit,
+    #[prop_or_default] pool: Option<PoolEntry>,
+    #[prop_or_default] anchor: Option<AttrValue>,
+) -> Html {
     let unit = &unit;
+    let details = use_route::<Route>().and_then(|r| r.unit(&unit.key));
 
     html! {
-      <div class="unit-card">
-        <div class="name row"><Text text={&unit.name} /></div>
+      <div class="unit-card" id={anchor}>
+        <div class="name row">
+          if let Some(to) = details {
+            <Link {to}><Text text={&unit.name} /></Link>
+          } else {
+            <Text text={&unit.name} />
+          }
+        </div>
         <div class="frame">
           <img class="image" title={&unit.name} src={&unit.image} />
           <SizeRow class="size-row row" {unit} />
@@ -58,22 +72,24 @@ pub fn unit_card(unit: Unit, #[prop_or_default] pool: Option<PoolEntry>) -> Html
 pub fn size_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
     use std::fmt::Write as _;
 
-    let mut soldiers_title = format!(
-        "{} {}",
-        unit.soldiers,
-        pluralize(unit.soldiers, "soldier", "soldiers")
+    let t = use_messages();
+    let mut soldiers_title = t.format(
+        pluralize(unit.soldiers, "{} soldier", "{} soldiers"),
+        &[&unit.soldiers],
     );
     if unit.officers > 0 {
         let _ = write!(
             soldiers_title,
-            "\n{} {}",
-            unit.officers,
-            pluralize(unit.officers, "officer", "officers")
+            "\n{}",
+            t.format(
+                pluralize(unit.officers, "{} officer", "{} officers"),
+                &[&unit.officers]
+            )
         );
     }
 
     let formations = unit.formations.iter().map(|f| {
-        let title = match &f {
+        let title = t.get(match &f {
             Formation::Square => "Square formation",
             Formation::Horde => "Horde",
             Formation::Phalanx => "Phalanx",
@@ -81,7 +97,7 @@ pub fn size_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
             Formation::Wedge => "Wedge formation",
             Formation::Schiltrom => "Schiltrom",
             Formation::ShieldWall => "Shield wall",
-        };
+        });
         html! {
           <Icon class="formation" {title} src="/icons/formation.svg" symbol={f.to_string()} />
         }
@@ -89,7 +105,7 @@ pub fn size_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
 
     html! {
       <div {class}>
-        <Icon class="icon" title="Soldiers" src="/icons/stat.svg" symbol="soldiers" />
+        <Icon class="icon" title={t.get("Soldiers")} src="/icons/stat.svg" symbol="soldiers" />
         <div class="size" title={soldiers_title}>
           <span class="soldiers">{ unit.soldiers }</span>
           if unit.officers > 0 {
@@ -106,13 +122,14 @@ pub fn size_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
 #[autoprops]
 #[function_component(CostRow)]
 pub fn cost_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
+    let t = use_messages();
     html! {
       <div {class}>
-        <Icon class="icon" title="Recruitment cost" src="/icons/stat.svg" symbol="cost" />
-        <div class="cost" title={format!("Cost: {}", unit.cost)}>
+        <Icon class="icon" title={t.get("Recruitment cost")} src="/icons/stat.svg" symbol="cost" />
+        <div class="cost" title={t.format("Cost: {}", &[&unit.cost])}>
           <span>{ unit.cost }</span>
           if unit.turns > 1 {
-            <div class="turns" title={format!("{} turns", unit.turns)}>
+            <div class="turns" title={t.format("{} turns", &[&unit.turns])}>
               <Icon class="attribute" height={512} width={512} src="/icons/attribute.svg" symbol="turns" />
               <span>{ unit.turns }</span>
             </div>
@@ -125,14 +142,15 @@ pub fn cost_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
 #[autoprops]
 #[function_component(UpkeepRow)]
 pub fn upkeep_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
+    let t = use_messages();
     html! {
       <div {class}>
         <Icon class="icon" src="/icons/stat.svg"
-            title={if unit.is_militia { "Upkeep cost (free in cities)" } else { "Upkeep cost"}}
+            title={t.get(if unit.is_militia { "Upkeep cost (free in cities)" } else { "Upkeep cost"})}
             symbol={if unit.is_militia { "upkeep-castle" } else { "upkeep" }}
         />
         <div class="upkeep"
-            title={format!("Upkeep{}: {}", if unit.is_militia { " (free in cities)" } else { "" }, unit.upkeep)}
+            title={t.format(if unit.is_militia { "Upkeep (free in cities): {}" } else { "Upkeep: {}" }, &[&unit.upkeep])}
         >
           <span>{ unit.upkeep }</span>
         </div>
@@ -143,18 +161,19 @@ pub fn upkeep_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
 #[autoprops]
 #[function_component(MentalRow)]
 pub fn mental_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
-    let discipline_tooltip = match unit.discipline {
+    let t = use_messages();
+    let discipline_tooltip = t.get(match unit.discipline {
         Discipline::Low => "Low discipline",
         Discipline::Normal => "Normal discipline",
         Discipline::Disciplined => "Disciplined",
         Discipline::Impetuous => "May charge without orders",
         Discipline::Berserker => "Berserker",
-    };
+    });
 
     html! {
       <div {class}>
         <Icon class="icon" title={discipline_tooltip} src="/icons/discipline.svg" symbol={unit.discipline.to_string()} />
-        <div class="mental" title={format!("Morale: {}", unit.morale)}>
+        <div class="mental" title={t.format("Morale: {}", &[&unit.morale])}>
           <span class="morale">{ unit.morale }</span>
           if unit.move_speed.is_some() || unit.stamina > 0 || unit.inexhaustible {
             <StaminaDetails unit={&unit} />
@@ -169,20 +188,29 @@ pub fn mental_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
 pub fn terrain_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
     use std::fmt::Write as _;
 
+    let t = use_messages();
     let mut title = "".to_string();
-    let mut write_details = |ground, v| {
+    let mut write_details = |penalty, bonus, v: i32| {
         if v != 0 {
             let _ = writeln!(
                 title,
-                "{ground} {}: {v:+}",
-                if v < 0 { "penalty" } else { "bonus" },
+                "{}",
+                t.format(if v < 0 { penalty } else { bonus }, &[&format!("{v:+}")])
             );
         }
     };
-    write_details("Scrub", unit.ground_bonus.scrub);
-    write_details("Forest", unit.ground_bonus.forest);
-    write_details("Sand", unit.ground_bonus.sand);
-    write_details("Snow", unit.ground_bonus.snow);
+    write_details(
+        "Scrub penalty: {}",
+        "Scrub bonus: {}",
+        unit.ground_bonus.scrub,
+    );
+    write_details(
+        "Forest penalty: {}",
+        "Forest bonus: {}",
+        unit.ground_bonus.forest,
+    );
+    write_details("Sand penalty: {}", "Sand bonus: {}", unit.ground_bonus.sand);
+    write_details("Snow penalty: {}", "Snow bonus: {}", unit.ground_bonus.snow);
     if title.len() > 0 {
         title.pop();
     }
@@ -223,21 +251,28 @@ fn stamina_details(unit: Unit) -> Html {
         )
     });
 
+    let t = use_messages();
     let mut title = if let Some((speed, _)) = &speed {
-        format!("Speed: {speed}\n")
+        t.format("Speed: {}", &[speed]) + "\n"
     } else {
         String::new()
     };
     if unit.inexhaustible {
-        let _ = write!(title, "Inexhaustible");
+        let _ = write!(title, "{}", t.get("Inexhaustible"));
     } else {
-        let _ = write!(title, "Stamina: {}", unit.stamina);
+        let _ = write!(title, "{}", t.format("Stamina: {}", &[&unit.stamina]));
         if unit.heat != 0 {
             let _ = write!(
                 title,
-                "\nHeat {}: {:+}",
-                if unit.heat > 0 { "penalty" } else { "bonus" },
-                -unit.heat,
+                "\n{}",
+                t.format(
+                    if unit.heat > 0 {
+                        "Heat penalty: {}"
+                    } else {
+                        "Heat bonus: {}"
+                    },
+                    &[&format!("{:+}", -unit.heat)]
+                ),
             );
         }
     };
@@ -269,20 +304,23 @@ fn stamina_details(unit: Unit) -> Html {
 pub fn weapon_row(#[prop_or_default] class: AttrValue, unit: Unit, weapon: Weapon) -> Html {
     use std::fmt::Write as _;
 
-    let mut title = match weapon.class {
-        WeaponType::Melee => "Melee weapon".to_string(),
-        WeaponType::Spear => "Spear".into(),
-        WeaponType::Missile => "Missile weapon".into(),
-        WeaponType::Thrown => "Thrown weapon".into(),
-        WeaponType::Gunpowder if unit.class == UnitClass::Artillery => "Cannon".into(),
-        WeaponType::Gunpowder => "Firearm".into(),
-    };
+    let t = use_messages();
+    let mut title = t
+        .get(match weapon.class {
+            WeaponType::Melee => "Melee weapon",
+            WeaponType::Spear => "Spear",
+            WeaponType::Missile => "Missile weapon",
+            WeaponType::Thrown => "Thrown weapon",
+            WeaponType::Gunpowder if unit.class == UnitClass::Artillery => "Cannon",
+            WeaponType::Gunpowder => "Firearm",
+        })
+        .to_string();
     let lethality = format!("{}%", (weapon.lethality * 100.0).round());
     if weapon.lethality != 1.0 {
-        let _ = write!(title, "\n    {} lethal", lethality);
+        let _ = write!(title, "\n    {}", t.format("{} lethal", &[&lethality]));
     }
     let strength = weapon.factor;
-    let _ = write!(title, "\n    Strength: {strength}");
+    let _ = write!(title, "\n    {}", t.format("Strength: {}", &[&strength]));
     let extra = if weapon.is_missile {
         weapon.range
     } else {
@@ -291,25 +329,32 @@ pub fn weapon_row(#[prop_or_default] class: AttrValue, unit: Unit, weapon: Weapo
     if extra > 0 {
         let _ = write!(
             title,
-            "\n    {}: {extra}",
-            if weapon.is_missile {
-                "Range"
-            } else {
-                "Charge bonus"
-            }
+            "\n    {}",
+            t.format(
+                if weapon.is_missile {
+                    "Range: {}"
+                } else {
+                    "Charge bonus: {}"
+                },
+                &[&extra]
+            )
         );
     }
     if weapon.is_missile && !unit.infinite_ammo {
-        let _ = write!(title, "\n    Ammo: {}", weapon.ammo);
+        let _ = write!(title, "\n    {}", t.format("Ammo: {}", &[&weapon.ammo]));
     }
     if weapon.spear_bonus > 0 {
-        let _ = write!(title, "\n    Bonus against cavalry: {}", weapon.spear_bonus);
+        let _ = write!(
+            title,
+            "\n    {}",
+            t.format("Bonus against cavalry: {}", &[&weapon.spear_bonus])
+        );
     }
     if weapon.armor_piercing {
-        let _ = write!(title, "\n    Armor piercing");
+        let _ = write!(title, "\n    {}", t.get("Armor piercing"));
     }
     if weapon.pre_charge {
-        let _ = write!(title, "\n    Thrown before charge");
+        let _ = write!(title, "\n    {}", t.get("Thrown before charge"));
     }
     let title: AttrValue = title.into();
 
@@ -364,16 +409,24 @@ pub fn defense_row(
 
     let symbol = if mount { "defense-mount" } else { "defense" };
 
+    let t = use_messages();
     let strength = def.total();
-    let mut title = format!("Defense{}: {strength}", if mount { " (mount)" } else { "" });
+    let mut title = t.format(
+        if mount {
+            "Defense (mount): {}"
+        } else {
+            "Defense: {}"
+        },
+        &[&strength],
+    );
     if def.armor > 0 {
-        let _ = write!(title, "\n    Armor: {}", def.armor);
+        let _ = write!(title, "\n    {}", t.format("Armor: {}", &[&def.armor]));
     }
     if def.skill > 0 {
-        let _ = write!(title, "\n    Skill: {}", def.skill);
+        let _ = write!(title, "\n    {}", t.format("Skill: {}", &[&def.skill]));
     }
     if def.shield > 0 {
-        let _ = write!(title, "\n    Shield: {}", def.shield);
+        let _ = write!(title, "\n    {}", t.format("Shield: {}", &[&def.shield]));
     }
     let title: AttrValue = title.into();
 
@@ -400,7 +453,7 @@ pub fn defense_row(
               }
             </div>
             if hp > 1 {
-              <div class="hp" title={format!("{hp} hit points")}>
+              <div class="hp" title={t.format("{} hit points", &[&hp])}>
                 <Icon class="ability" src="/icons/ability.svg" symbol="heart" />
                 <span>{ hp }</span>
               </div>
@@ -427,20 +480,42 @@ fn ground_bonus(#[prop_or_default] class: AttrValue, ground: AttrValue, value: i
     }
 }
 
+pub fn ability_title(ability: Ability) -> &'static str {
+    match ability {
+        Ability::CantHide => "Cannot hide",
+        Ability::HideImprovedForest => "Can hide well in forests",
+        Ability::HideLongGrass => "Can hide in long grass",
+        Ability::HideAnywhere => "Can hide anywhere",
+        Ability::FrightenFoot => "Frightens nearby infantry",
+        Ability::FrightenMounted => "Frightens nearby cavalry",
+        Ability::FrightenAll => "Frightens nearby units",
+        Ability::CanRunAmok => "Can run amok",
+        Ability::CantabrianCircle => "Can form Cantabrian circle",
+        Ability::Command => "Inspires nearby units",
+        Ability::Warcry => "Can perform warcry to increase attack",
+        Ability::PowerCharge => "Powerful charge",
+        Ability::Chant => "Can chant to affect morale",
+        Ability::FormedCharge => "Can do formed charge",
+        Ability::Stakes => "Can lay defensive stakes",
+        Ability::Knight => "Receives knightly bonuses",
+    }
+}
+
 #[autoprops]
 #[function_component(AbilitiesRow)]
 pub fn abilities_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
+    let t = use_messages();
     let base = if unit.class == UnitClass::Ship {
         [html! {
-          <Icon class="ability" title="Ship" src="/icons/class.svg" symbol="ship" />
+          <Icon class="ability" title={t.get("Ship")} src="/icons/class.svg" symbol="ship" />
         }]
     } else if unit.class == UnitClass::Artillery {
         [html! {
-          <Icon class="ability" title="Artillery" src="/icons/class.svg" symbol="artillery" />
+          <Icon class="ability" title={t.get("Artillery")} src="/icons/class.svg" symbol="artillery" />
         }]
     } else if unit.class == UnitClass::General {
         [html! {
-          <Icon class="ability" title="General" src="/icons/class.svg" symbol="general" />
+          <Icon class="ability" title={t.get("General")} src="/icons/class.svg" symbol="general" />
         }]
     } else {
         [html! { <></> }]
@@ -448,43 +523,26 @@ pub fn abilities_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
     .into_iter();
     let mount = if unit.mount == MountType::Horse {
         [html! {
-          <Icon class="ability" title="Horse" src="/icons/mount.svg" symbol="horse" />
+          <Icon class="ability" title={t.get("Horse")} src="/icons/mount.svg" symbol="horse" />
         }]
     } else if unit.mount == MountType::Camel {
         [html! {
-          <Icon class="ability" title="Camel" src="/icons/mount.svg" symbol="camel" />
+          <Icon class="ability" title={t.get("Camel")} src="/icons/mount.svg" symbol="camel" />
         }]
     } else if unit.mount == MountType::Elephant {
         [html! {
-          <Icon class="ability" title="Elephant" src="/icons/mount.svg" symbol="elephant" />
+          <Icon class="ability" title={t.get("Elephant")} src="/icons/mount.svg" symbol="elephant" />
         }]
     } else if unit.mount == MountType::Chariot {
         [html! {
-          <Icon class="ability" title="Chariot" src="/icons/mount.svg" symbol="chariot" />
+          <Icon class="ability" title={t.get("Chariot")} src="/icons/mount.svg" symbol="chariot" />
         }]
     } else {
         [html! { <></> }]
     }
     .into_iter();
     let abilities = base.chain(mount).chain(unit.abilities.iter().map(|ab| {
-        let title = match ab {
-            Ability::CantHide => "Cannot hide",
-            Ability::HideImprovedForest => "Can hide well in forests",
-            Ability::HideLongGrass => "Can hide in long grass",
-            Ability::HideAnywhere => "Can hide anywhere",
-            Ability::FrightenFoot => "Frightens nearby infantry",
-            Ability::FrightenMounted => "Frightens nearby cavalry",
-            Ability::FrightenAll => "Frightens nearby units",
-            Ability::CanRunAmok => "Can run amok",
-            Ability::CantabrianCircle => "Can form Cantabrian circle",
-            Ability::Command => "Inspires nearby units",
-            Ability::Warcry => "Can perform warcry to increase attack",
-            Ability::PowerCharge => "Powerful charge",
-            Ability::Chant => "Can chant to affect morale",
-            Ability::FormedCharge => "Can do formed charge",
-            Ability::Stakes => "Can lay defensive stakes",
-            Ability::Knight => "Receives knightly bonuses",
-        };
+        let title = t.get(ability_title(ab));
         html! {
           <Icon class="ability" {title} src="/icons/ability.svg" symbol={ab.to_string()} />
         }
@@ -500,10 +558,16 @@ pub fn abilities_row(#[prop_or_default] class: AttrValue, unit: Unit) -> Html {
 #[autoprops]
 #[function_component(PoolRow)]
 pub fn pool_row(#[prop_or_default] class: AttrValue, pool: PoolEntry) -> Html {
+    let t = use_messages();
     let p05 = pool.replenish.p05();
     let p50 = pool.replenish.p50();
     let p95 = pool.replenish.p95();
-    let details: AttrValue = format!("Units replenish:\nin {p05} turns (5% of the time)\nin {p50} turns (50% of the time)\nin {p95} turns (95% of the time)").into();
+    let details: AttrValue = t
+        .format(
+            "Units replenish:\nin {} turns (5% of the time)\nin {} turns (50% of the time)\nin {} turns (95% of the time)",
+            &[&p05, &p50, &p95],
+        )
+        .into();
     let range = max(p95 - p50, p50 - p05);
     let max = pool.max;
     let exp = pool.exp;
@@ -515,9 +579,9 @@ pub fn pool_row(#[prop_or_default] class: AttrValue, pool: PoolEntry) -> Html {
           <div class="average">{ p50 }</div>
           <div class="interval">{ format!("±{range}") }</div>
         </div>
-        <div class="max" title={format!("Max: {max} units")} >{ format!("×{max}") }</div>
+        <div class="max" title={t.format("Max: {} units", &[&max])} >{ format!("×{max}") }</div>
         if pool.exp > 0 {
-          <Icon class="exp" title={format!("{exp} experience")} src="/icons/exp.svg" symbol={format!("exp-{exp}")} />
+          <Icon class="exp" title={t.format("{} experience", &[&exp])} src="/icons/exp.svg" symbol={format!("exp-{exp}")} />
         }
         // TODO restricts
         // <template v-if="faction">
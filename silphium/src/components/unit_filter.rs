@@ -1,17 +1,54 @@
 use implicit_clone::ImplicitClone;
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
 use yew::prelude::*;
 
-use crate::{hooks::ModelHandle, model::Unit};
+use crate::{
+    hooks::ModelHandle,
+    model::{Ability, MountType, Unit, UnitClass, WeaponType},
+};
 
-#[derive(PartialEq, Clone, ImplicitClone, Default)]
+#[serde_as]
+#[serde_with::apply(
+    Option => #[serde(skip_serializing_if = "Option::is_none")],
+)]
+#[derive(PartialEq, Clone, ImplicitClone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct UnitFilter {
     pub era: Option<AttrValue>,
     pub horde: Option<bool>,
     pub regional: Option<bool>,
+
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub class: Option<UnitClass>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub mount: Option<MountType>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub ability: Option<Ability>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub weapon: Option<WeaponType>,
+    pub armor_piercing: Option<bool>,
+
+    pub min_cost: Option<u32>,
+    pub max_cost: Option<u32>,
+    pub min_morale: Option<u32>,
+    pub max_morale: Option<u32>,
+    pub min_armor: Option<u32>,
+    pub max_armor: Option<u32>,
+}
+
+fn in_range(value: u32, min: Option<u32>, max: Option<u32>) -> bool {
+    min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
 }
 
 impl UnitFilter {
     pub fn apply(&self, unit: &Unit) -> bool {
+        let weapons = || {
+            unit.primary_weapon
+                .iter()
+                .chain(unit.secondary_weapon.iter())
+        };
+
         (if let Some(ref era) = self.era {
             unit.eras.contains(era)
         } else {
@@ -24,7 +61,44 @@ impl UnitFilter {
             unit.is_regional == regional
         } else {
             true
-        })
+        }) && (if let Some(class) = self.class {
+            unit.class == class
+        } else {
+            true
+        }) && (if let Some(mount) = self.mount {
+            unit.mount == mount
+        } else {
+            true
+        }) && (if let Some(ability) = self.ability {
+            unit.abilities.contains(&ability)
+        } else {
+            true
+        }) && (if let Some(weapon) = self.weapon {
+            weapons().any(|w| w.class == weapon)
+        } else {
+            true
+        }) && (if let Some(armor_piercing) = self.armor_piercing {
+            weapons().any(|w| w.armor_piercing) == armor_piercing
+        } else {
+            true
+        }) && in_range(unit.cost, self.min_cost, self.max_cost)
+            && in_range(unit.morale, self.min_morale, self.max_morale)
+            && in_range(unit.defense.armor, self.min_armor, self.max_armor)
+    }
+
+    /// Whether any of the filters beyond the era, horde, and regional toggles is set.
+    pub fn is_refined(&self) -> bool {
+        *self != self.cleared()
+    }
+
+    /// Resets the filters beyond the era, horde, and regional toggles.
+    pub fn cleared(&self) -> Self {
+        UnitFilter {
+            era: self.era.clone(),
+            horde: self.horde,
+            regional: self.regional,
+            ..Default::default()
+        }
     }
 }
 
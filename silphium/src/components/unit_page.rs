@@ -0,0 +1,307 @@
+use implicit_clone::unsync::IString;
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+use yew_router::prelude::*;
+
+use crate::{
+    AppContext, Messages,
+    components::{BackLink, Link, Text, UnitCard, ability_title, class_title, pool_unit_anchor},
+    hooks::use_messages,
+    model::{Defense, Module, Unit, Weapon},
+    routes::{MERCS, Route},
+};
+
+fn find_unit(module: &Module, faction_id: &str, key: &str) -> Option<Unit> {
+    if faction_id == MERCS {
+        module
+            .pools
+            .iter()
+            .flat_map(|p| p.units.iter().map(|e| e.unit).collect::<Vec<_>>())
+            .find(|u| u.key == key)
+    } else {
+        let faction_id = module
+            .aliases
+            .get(faction_id)
+            .map(|a| a.as_str())
+            .unwrap_or(faction_id);
+        module
+            .factions
+            .get(faction_id)?
+            .roster
+            .iter()
+            .find(|u| u.key == key)
+    }
+}
+
+fn weapon_summary(t: &Messages, weapon: &Weapon) -> String {
+    let mut parts = vec![t.format("attack {}", &[&weapon.factor])];
+    if weapon.is_missile {
+        parts.push(t.format("range {}", &[&weapon.range]));
+        if weapon.ammo > 0 {
+            parts.push(t.format("ammo {}", &[&weapon.ammo]));
+        }
+    } else {
+        parts.push(t.format("charge {}", &[&weapon.charge]));
+    }
+    if weapon.lethality != 1.0 {
+        let lethality = format!("{}%", (weapon.lethality * 100.0).round());
+        parts.push(t.format("{} lethal", &[&lethality]));
+    }
+    if weapon.spear_bonus > 0 {
+        parts.push(t.format("+{} against cavalry", &[&weapon.spear_bonus]));
+    }
+    for (flag, name) in [
+        (weapon.armor_piercing, "armor piercing"),
+        (weapon.body_piercing, "body piercing"),
+        (weapon.pre_charge, "thrown before charge"),
+        (weapon.launching, "knocks back"),
+        (weapon.area, "area attack"),
+        (weapon.fire, "fire"),
+    ] {
+        if flag {
+            parts.push(t.get(name).to_string());
+        }
+    }
+    parts.join(", ")
+}
+
+fn defense_summary(t: &Messages, defense: &Defense, hp: u32) -> String {
+    let mut summary = t.format(
+        "{} (armor {}, skill {}, shield {})",
+        &[
+            &defense.total(),
+            &defense.armor,
+            &defense.skill,
+            &defense.shield,
+        ],
+    );
+    if hp > 1 {
+        summary += ", ";
+        summary += &t.format("{} hit points", &[&hp]);
+    }
+    summary
+}
+
+fn stat_rows(t: &Messages, module: &Module, unit: &Unit) -> Vec<(&'static str, String)> {
+    let mut rows = vec![
+        ("Class", t.get(class_title(unit.class)).to_string()),
+        (
+            "Soldiers",
+            if unit.officers > 0 {
+                t.format("{} and {} officers", &[&unit.soldiers, &unit.officers])
+            } else {
+                unit.soldiers.to_string()
+            },
+        ),
+        ("Recruitment cost", unit.cost.to_string()),
+        ("Upkeep", unit.upkeep.to_string()),
+    ];
+    if unit.turns > 1 {
+        rows.push(("Recruitment turns", unit.turns.to_string()));
+    }
+    if let Some(ref weapon) = unit.primary_weapon {
+        rows.push(("Primary weapon", weapon_summary(t, weapon)));
+    }
+    if let Some(ref weapon) = unit.secondary_weapon {
+        rows.push(("Secondary weapon", weapon_summary(t, weapon)));
+    }
+    rows.push(("Defense", defense_summary(t, &unit.defense, unit.hp)));
+    if unit.mount.has_mount_stats() {
+        rows.push((
+            "Mount defense",
+            defense_summary(t, &unit.defense_mount, unit.hp_mount),
+        ));
+    }
+    rows.push(("Morale", unit.morale.to_string()));
+    rows.push(("Discipline", unit.discipline.to_string()));
+    if unit.inexhaustible {
+        rows.push(("Stamina", t.get("inexhaustible").to_string()));
+    } else if unit.stamina > 0 {
+        rows.push(("Stamina", unit.stamina.to_string()));
+    }
+    if unit.heat != 0 {
+        rows.push(("Heat penalty", unit.heat.to_string()));
+    }
+    if let Some(speed) = unit.move_speed {
+        rows.push(("Speed", speed.to_string()));
+    }
+    let ground: Vec<_> = [
+        ("scrub", unit.ground_bonus.scrub),
+        ("forest", unit.ground_bonus.forest),
+        ("sand", unit.ground_bonus.sand),
+        ("snow", unit.ground_bonus.snow),
+    ]
+    .into_iter()
+    .filter(|(_, v)| *v != 0)
+    .map(|(ground, v)| format!("{} {v:+}", t.get(ground)))
+    .collect();
+    if !ground.is_empty() {
+        rows.push(("Terrain", ground.join(", ")));
+    }
+    if !unit.abilities.is_empty() {
+        let abilities: Vec<_> = unit
+            .abilities
+            .iter()
+            .map(|a| t.get(ability_title(a)))
+            .collect();
+        rows.push(("Abilities", abilities.join(", ")));
+    }
+    if !unit.eras.is_empty() {
+        let eras: Vec<_> = unit
+            .eras
+            .iter()
+            .map(|e| {
+                module
+                    .eras
+                    .get(&e)
+                    .map(|era| era.name.clone())
+                    .unwrap_or(e)
+                    .to_string()
+            })
+            .collect();
+        rows.push(("Eras", eras.join(", ")));
+    }
+    rows
+}
+
+#[autoprops]
+#[function_component(UnitPage)]
+pub fn unit_page(module_id: AttrValue, faction_id: AttrValue, unit_key: AttrValue) -> Html {
+    let ctx = use_context::<AppContext>().expect("no context");
+    let module = &ctx.modules[&module_id];
+    let t = &use_messages();
+    let Some(unit) = find_unit(module, &faction_id, &unit_key) else {
+        return html! { <Redirect<Route> to={Route::Module { module: module_id }} /> };
+    };
+    let description = module
+        .descriptions
+        .get(&unit.key)
+        .cloned()
+        .unwrap_or_default();
+
+    let (source_name, source_image): (IString, IString) = if faction_id == MERCS {
+        (t.get("Mercenaries"), "/icons/ui/mercs.webp".into())
+    } else {
+        let faction_id = module.aliases.get(&faction_id).unwrap_or(&faction_id);
+        let faction = &module.factions[faction_id];
+        (faction.name.clone(), faction.image.clone())
+    };
+
+    let rows = stat_rows(t, module, &unit)
+        .into_iter()
+        .map(|(name, value)| {
+            html! {
+              <tr>
+                <th>{ t.get(name) }</th>
+                <td>{ value }</td>
+              </tr>
+            }
+        });
+
+    let factions: Vec<_> = module
+        .factions
+        .values()
+        .filter(|f| f.roster.iter().any(|u| u.key == unit.key))
+        .map(|f| {
+            let to = Route::Unit {
+                module: module.id.clone(),
+                faction: f.id_or_alias(),
+                unit: unit.key.clone(),
+            };
+            html! {
+              <li>
+                <Link {to}>
+                  <img class="icon" src={&f.image} title={&f.name} />
+                  <span class="name"><Text text={&f.name} /></span>
+                </Link>
+              </li>
+            }
+        })
+        .collect();
+
+    let recruitment = unit.recruitment.iter().map(|line| {
+        html! { <li>{ line }</li> }
+    });
+
+    let merc_route = Route::Mercenaries {
+        module: module.id.clone(),
+    };
+    let pools: Vec<_> = module
+        .pools
+        .iter()
+        .filter_map(|p| {
+            let entry = p.units.iter().find(|e| e.unit.key == unit.key)?;
+            let href = format!(
+                "{}#{}",
+                ctx.href(&merc_route),
+                pool_unit_anchor(&p, &entry.unit)
+            );
+            let name = if p.name.is_empty() { p.id.clone() } else { p.name.clone() };
+            Some(html! {
+              <li>
+                <a {href}><Text text={name} /></a>
+                <span class="details">
+                  { t.format("cost {}, up to {}, {} regions", &[&entry.unit.cost, &entry.max, &p.regions.len()]) }
+                </span>
+              </li>
+            })
+        })
+        .collect();
+
+    html! {
+    <div class="unit-page">
+      <header class="header-container">
+        <div class="nav">
+          <BackLink />
+        </div>
+        <div class="header">
+          <div class="name"><Text text={&unit.name} /></div>
+          <div class="source"><Text text={source_name} /></div>
+        </div>
+        <img class="icon" src={source_image} />
+      </header>
+      <main>
+        <div class="unit-details">
+          <UnitCard unit={&unit} />
+          <div class="descriptions">
+            if !description.short.is_empty() {
+              <p class="short"><Text text={&description.short} /></p>
+            }
+            if !description.long.is_empty() {
+              <p class="long"><Text text={&description.long} /></p>
+            }
+          </div>
+        </div>
+        <table class="stat-block">
+          <tbody>
+            {for rows}
+          </tbody>
+        </table>
+        if !unit.recruitment.is_empty() {
+          <section class="cross-references">
+            <h2>{t.get("Recruitment")}</h2>
+            <ul class="recruitment">
+              {for recruitment}
+            </ul>
+          </section>
+        }
+        if !factions.is_empty() {
+          <section class="cross-references">
+            <h2>{t.get("Fielded by")}</h2>
+            <ul class="factions">
+              {for factions}
+            </ul>
+          </section>
+        }
+        if !pools.is_empty() {
+          <section class="cross-references">
+            <h2>{t.get("Hired from")}</h2>
+            <ul class="pools">
+              {for pools}
+            </ul>
+          </section>
+        }
+      </main>
+    </div>
+    }
+}
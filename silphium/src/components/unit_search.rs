@@ -0,0 +1,140 @@
+use implicit_clone::unsync::IString;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_autoprops::autoprops;
+
+use crate::{
+    AppContext, Messages,
+    components::{Text, ability_title},
+    model::{Module, Pool, Unit},
+    routes::Route,
+};
+
+const MAX_RESULTS: usize = 50;
+
+pub fn unit_anchor(unit: &Unit) -> AttrValue {
+    format!("unit-{}", unit.id).into()
+}
+
+pub fn pool_unit_anchor(pool: &Pool, unit: &Unit) -> AttrValue {
+    format!("pool-{}-{}", pool.id, unit.id).into()
+}
+
+struct SearchHit {
+    unit: Unit,
+    sources: Vec<(IString, String)>,
+}
+
+fn matches(t: &Messages, unit: &Unit, terms: &[String]) -> bool {
+    let mut haystack = format!("{} {}", unit.name, unit.key).to_lowercase();
+    for ab in unit.abilities.iter() {
+        haystack.push(' ');
+        haystack.push_str(&ab.to_string());
+        haystack.push(' ');
+        haystack.push_str(&ability_title(ab).to_lowercase());
+        haystack.push(' ');
+        haystack.push_str(&t.get(ability_title(ab)).to_lowercase());
+    }
+    terms.iter().all(|t| haystack.contains(t.as_str()))
+}
+
+fn search(ctx: &AppContext, module: &Module, query: &str) -> Vec<SearchHit> {
+    let t = &ctx.messages;
+    let terms: Vec<_> = query.split_whitespace().map(str::to_lowercase).collect();
+    let mut hits: Vec<SearchHit> = vec![];
+    if terms.is_empty() {
+        return hits;
+    }
+
+    let mut add_hit = |unit: &Unit, name: IString, href: String| {
+        if let Some(hit) = hits.iter_mut().find(|h| h.unit.id == unit.id) {
+            hit.sources.push((name, href));
+        } else {
+            hits.push(SearchHit {
+                unit: unit.clone(),
+                sources: vec![(name, href)],
+            });
+        }
+    };
+
+    for faction in module.factions.values() {
+        let route = Route::Faction {
+            module: module.id.clone(),
+            faction: faction.id_or_alias(),
+        };
+        for unit in faction.roster.iter().filter(|u| matches(t, u, &terms)) {
+            let href = format!("{}#{}", ctx.href(&route), unit_anchor(&unit));
+            add_hit(&unit, faction.name.clone(), href);
+        }
+    }
+
+    let route = Route::Mercenaries {
+        module: module.id.clone(),
+    };
+    for pool in module.pools.iter() {
+        let name: IString = if pool.name.len() > 0 {
+            pool.name.clone()
+        } else {
+            t.get("Mercenaries")
+        };
+        for entry in pool.units.iter().filter(|e| matches(t, &e.unit, &terms)) {
+            let href = format!(
+                "{}#{}",
+                ctx.href(&route),
+                pool_unit_anchor(&pool, &entry.unit)
+            );
+            add_hit(&entry.unit, name.clone(), href);
+        }
+    }
+
+    hits
+}
+
+#[autoprops]
+#[function_component(UnitSearch)]
+pub fn unit_search(module: Module) -> Html {
+    let ctx = use_context::<AppContext>().expect("no context");
+    let t = &ctx.messages;
+    let query = use_state(String::new);
+
+    let oninput = {
+        let query = query.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            query.set(input.value());
+        })
+    };
+
+    let hits = search(&ctx, &module, &query);
+    let count = hits.len();
+    let results = hits.into_iter().take(MAX_RESULTS).map(|hit| {
+        let sources = hit.sources.into_iter().map(|(name, href)| {
+            html! {
+              <a class="source" {href}><Text text={name} /></a>
+            }
+        });
+        html! {
+          <li>
+            <span class="name"><Text text={&hit.unit.name} /></span>
+            <span class="key">{ &hit.unit.key }</span>
+            <span class="sources">{for sources}</span>
+          </li>
+        }
+    });
+
+    html! {
+      <div class="unit-search">
+        <input type="search" placeholder={t.get("Search units by name, key or ability")} value={(*query).clone()} {oninput} />
+        if query.trim().len() > 0 {
+          if count == 0 {
+            <div class="summary">{t.get("No units found")}</div>
+          } else if count > MAX_RESULTS {
+            <div class="summary">{ t.format("Showing {} of {} units", &[&MAX_RESULTS, &count]) }</div>
+          }
+          <ul class="results">
+            {for results}
+          </ul>
+        }
+      </div>
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::{BTreeSet, HashMap};
+
+use implicit_clone::unsync::IString;
+
+use crate::model::{Faction, Requirement};
+
+/// Decides the conditions of a requirement, like the evaluator used when the
+/// site is generated. A condition without a choice is unknown, and unknown
+/// conditions are left out of the result. The one difference is that a port
+/// can be chosen here, while the site generator has no choice for it and
+/// always uses the default.
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct Evaluator {
+    /// The result of conditions this evaluator has no choices for.
+    pub default: Option<bool>,
+    pub faction: Option<Choices>,
+    pub hidden_resource: Option<Choices>,
+    pub major_event: Option<Choices>,
+    /// The level of each building chain present, or `None` for chains known
+    /// to be absent.
+    pub building: HashMap<IString, Option<u32>>,
+    pub port: Option<bool>,
+}
+
+impl Evaluator {
+    pub fn faction(faction: &Faction) -> Self {
+        Self {
+            faction: Some(Choices {
+                map: [
+                    (faction.id.clone(), true),
+                    (faction.culture.clone(), true),
+                    ("all".into(), true),
+                ]
+                .into(),
+                default: Some(false),
+            }),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct Choices {
+    pub map: HashMap<IString, bool>,
+    pub default: Option<bool>,
+}
+
+impl Choices {
+    fn get(&self, choice: &str) -> Option<bool> {
+        self.map.get(choice).copied().or(self.default)
+    }
+}
+
+pub fn evaluate(
+    req: &Requirement,
+    aliases: &HashMap<IString, Requirement>,
+    eval: &Evaluator,
+) -> bool {
+    do_evaluate(req, aliases, eval).unwrap_or(true)
+}
+
+fn do_evaluate(
+    req: &Requirement,
+    aliases: &HashMap<IString, Requirement>,
+    eval: &Evaluator,
+) -> Option<bool> {
+    match req {
+        Requirement::None | Requirement::Unknown => eval.default,
+        Requirement::False => Some(false),
+        Requirement::HiddenResource(id) => eval.hidden_resource.as_ref().and_then(|r| r.get(id)),
+        Requirement::MajorEvent(event) => eval.major_event.as_ref().and_then(|r| r.get(event)),
+        Requirement::BuildingPresent(chain, level) => eval
+            .building
+            .get(chain)
+            .map(|present| present.is_some_and(|present| present >= *level)),
+        Requirement::Factions(factions) => {
+            let res: Vec<_> = factions
+                .iter()
+                .filter_map(|id| eval.faction.as_ref().and_then(|r| r.get(&id)))
+                .collect();
+            if res.is_empty() {
+                None
+            } else {
+                Some(res.iter().any(|x| *x))
+            }
+        }
+        Requirement::Port => eval.port.or(eval.default),
+        Requirement::IsPlayer => Some(true),
+        Requirement::Alias(id) => {
+            do_evaluate(aliases.get(id).unwrap_or(&Requirement::None), aliases, eval)
+        }
+        Requirement::Not(req) => do_evaluate(req, aliases, eval).map(|r| !r),
+        Requirement::And(items) => {
+            let res: Vec<_> = items
+                .iter()
+                .filter_map(|item| do_evaluate(item, aliases, eval))
+                .collect();
+            if res.is_empty() {
+                None
+            } else {
+                Some(res.iter().all(|x| *x))
+            }
+        }
+        Requirement::Or(items) => {
+            let res: Vec<_> = items
+                .iter()
+                .filter_map(|item| do_evaluate(item, aliases, eval))
+                .collect();
+            if res.is_empty() {
+                None
+            } else {
+                Some(res.iter().any(|x| *x))
+            }
+        }
+    }
+}
+
+/// The conditions that appear in a set of requirements, with aliases expanded.
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct Conditions {
+    pub hidden_resources: BTreeSet<IString>,
+    pub major_events: BTreeSet<IString>,
+    pub buildings: BTreeSet<IString>,
+    pub port: bool,
+}
+
+impl Conditions {
+    pub fn collect(&mut self, req: &Requirement, aliases: &HashMap<IString, Requirement>) {
+        match req {
+            Requirement::HiddenResource(id) => {
+                self.hidden_resources.insert(id.clone());
+            }
+            Requirement::MajorEvent(event) => {
+                self.major_events.insert(event.clone());
+            }
+            Requirement::BuildingPresent(chain, _) => {
+                self.buildings.insert(chain.clone());
+            }
+            Requirement::Port => self.port = true,
+            Requirement::Alias(id) => {
+                if let Some(req) = aliases.get(id) {
+                    self.collect(req, aliases);
+                }
+            }
+            Requirement::Not(req) => self.collect(req, aliases),
+            Requirement::And(items) | Requirement::Or(items) => {
+                for item in items {
+                    self.collect(item, aliases);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hidden_resources.is_empty()
+            && self.major_events.is_empty()
+            && self.buildings.is_empty()
+            && !self.port
+    }
+}
@@ -0,0 +1,11 @@
+use yew::prelude::*;
+
+use crate::{AppContext, i18n::Messages};
+
+/// The translations of the UI texts in the language of the page.
+#[hook]
+pub fn use_messages() -> Messages {
+    use_context::<AppContext>()
+        .map(|ctx| ctx.messages)
+        .unwrap_or_default()
+}
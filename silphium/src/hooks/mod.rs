@@ -1,2 +1,6 @@
+mod messages;
 mod model_handle;
-pub use model_handle::*;
\ No newline at end of file
+mod query_model;
+pub use messages::*;
+pub use model_handle::*;
+pub use query_model::*;
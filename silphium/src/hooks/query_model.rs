@@ -0,0 +1,27 @@
+use serde::{Serialize, de::DeserializeOwned};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+use crate::{hooks::ModelHandle, routes::Route};
+
+/// Keeps a model in the query string of the current route, so that it can be
+/// shared as a link. Uses `init` when the query string is empty.
+#[hook]
+pub fn use_query_model<T, F>(init: F) -> ModelHandle<T>
+where
+    T: Serialize + DeserializeOwned + 'static,
+    F: FnOnce() -> T,
+{
+    let navigator = use_navigator();
+    let route = use_route::<Route>();
+    let value = use_location()
+        .filter(|l| l.query_str().len() > 1)
+        .and_then(|l| l.query::<T>().ok())
+        .unwrap_or_else(init);
+
+    ModelHandle::new(value, move |value: T| {
+        if let (Some(navigator), Some(route)) = (&navigator, &route) {
+            let _ = navigator.replace_with_query(route, &value);
+        }
+    })
+}
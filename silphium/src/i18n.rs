@@ -0,0 +1,57 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write as _},
+};
+
+use implicit_clone::{ImplicitClone, unsync::IString};
+use serde::{Deserialize, Serialize};
+use yew::prelude::*;
+
+/// A language the site is shown in. The first language of a site is at its
+/// root, the others under their id.
+#[derive(PartialEq, Serialize, Deserialize, ImplicitClone, Clone, Debug)]
+pub struct Language {
+    #[serde(rename = "i")]
+    pub id: IString,
+    #[serde(rename = "n")]
+    pub name: IString,
+}
+
+/// The translations of the UI texts, keyed by their English text. Texts
+/// without a translation are shown in English.
+#[derive(PartialEq, Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(transparent)]
+pub struct Messages(pub HashMap<IString, IString>);
+
+impl Messages {
+    pub fn get(&self, text: &'static str) -> IString {
+        self.0.get(text).cloned().unwrap_or(IString::Static(text))
+    }
+
+    /// Translates a text with `{}` placeholders, and fills them in order.
+    pub fn format(&self, text: &'static str, args: &[&dyn Display]) -> String {
+        let text = self.get(text);
+        let mut parts = text.split("{}");
+        let mut args = args.iter();
+        let mut res = parts.next().unwrap_or_default().to_string();
+        for part in parts {
+            if let Some(arg) = args.next() {
+                let _ = write!(res, "{arg}");
+            }
+            res.push_str(part);
+        }
+        res
+    }
+
+    /// Translates a text with `{}` placeholders, and fills them in order with
+    /// markup, such as links.
+    pub fn format_html(&self, text: &'static str, args: &[Html]) -> Html {
+        let text = self.get(text);
+        let mut args = args.iter().cloned();
+        let parts = text.split("{}").enumerate().map(|(i, part)| {
+            let arg = if i > 0 { args.next() } else { None };
+            html! { <>{arg}{part.to_string()}</> }
+        });
+        html! { <>{for parts}</> }
+    }
+}
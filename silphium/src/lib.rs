@@ -7,6 +7,7 @@ use gloo::net::http::Request;
 use implicit_clone::ImplicitClone;
 use implicit_clone::unsync::{IArray, IString};
 use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::wasm_bindgen;
 use yew::prelude::*;
 use yew::suspense::use_future;
@@ -14,39 +15,101 @@ use yew_autoprops::autoprops;
 use yew_router::prelude::*;
 
 mod components;
+pub mod eval;
 mod hooks;
+mod i18n;
 pub mod model;
 mod routes;
 
-pub use routes::Route;
+pub use i18n::{Language, Messages};
+pub use routes::{MERCS, Route};
 pub type ModuleMap = IndexMap<IString, Module>;
 
+/// The name of the meta tag that gives the path of the pages of a language
+/// other than the first.
+pub const BASE_META: &str = "faust-base";
+
+/// The catalog of a site in one of its languages, as kept in `mods.cbor`.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Default, Debug)]
+pub struct Catalog {
+    #[serde(rename = "m")]
+    pub modules: ModuleMap,
+    /// The id of the language of this catalog, empty when the site is not
+    /// translated.
+    #[serde(rename = "l", default)]
+    pub language: IString,
+    #[serde(rename = "L", default)]
+    pub languages: IArray<Language>,
+    #[serde(rename = "t", default)]
+    pub messages: Messages,
+}
+
 #[derive(ImplicitClone, Clone, PartialEq)]
 struct AppContext {
     modules: ModuleMap,
+    language: IString,
+    languages: IArray<Language>,
+    messages: Messages,
+    /// The path the pages in this language are under, empty at the root.
+    base: IString,
+}
+
+impl AppContext {
+    fn new(catalog: Catalog, base: IString) -> Self {
+        Self {
+            modules: catalog.modules,
+            language: catalog.language,
+            languages: catalog.languages,
+            messages: catalog.messages,
+            base,
+        }
+    }
+
+    fn basename(&self) -> Option<AttrValue> {
+        (!self.base.is_empty()).then(|| self.base.clone())
+    }
+
+    /// The link to a route, for anchors that do not go through the router.
+    fn href(&self, route: &Route) -> String {
+        format!("{}{}", self.base, route.to_path())
+    }
+}
+
+/// The path of the pages of the current language, from the meta tag written
+/// in the page.
+fn page_base() -> IString {
+    web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| {
+            d.query_selector(&format!("meta[name={BASE_META}]"))
+                .ok()
+                .flatten()
+        })
+        .and_then(|meta| meta.get_attribute("content"))
+        .map(IString::from)
+        .unwrap_or_default()
 }
 
 #[function_component(AppContent)]
 fn app_content() -> HtmlResult {
+    let base = page_base();
     let context = {
-        let res = use_future(async || {
-            Request::get("/mods.cbor")
-                .send()
-                .await
-                .unwrap()
-                .binary()
-                .await
-        })?;
-        let modules: ModuleMap = ciborium::from_reader(res.as_ref().unwrap().as_slice()).unwrap();
-        AppContext { modules }
+        let url = format!("{base}/mods.cbor");
+        let res =
+            use_future(
+                move || async move { Request::get(&url).send().await.unwrap().binary().await },
+            )?;
+        let catalog: Catalog = ciborium::from_reader(res.as_ref().unwrap().as_slice()).unwrap();
+        AppContext::new(catalog, base)
     };
+    let basename = context.basename();
 
     Ok(html! {
       <ContextProvider<AppContext> {context}>
-        <BrowserRouter>
+        <BrowserRouter {basename}>
           <Switch<Route> render={switch} />
+          <Footer />
         </BrowserRouter>
-        <Footer />
       </ContextProvider<AppContext>>
     })
 }
@@ -65,43 +128,69 @@ fn app() -> HtmlResult {
 #[autoprops]
 #[function_component(Footer)]
 fn footer() -> Html {
+    let ctx = use_context::<AppContext>().expect("no context");
+    let route = use_route::<Route>().unwrap_or_default();
+    let t = &ctx.messages;
+
+    let languages = ctx.languages.iter().enumerate().map(|(i, language)| {
+        let base = if i == 0 {
+            String::new()
+        } else {
+            format!("/{}", language.id)
+        };
+        let href = format!("{base}{}", route.to_path());
+        let class = classes!((language.id == ctx.language).then_some("current"));
+        html! {
+          <a {href} {class} lang={&language.id}>{ &language.name }</a>
+        }
+    });
+
     html! {
       <footer>
-        <span>{"Generated with "}<a href="https://faust.rmf.io">{"FAUST"}</a>{" with this "}<a href="/faust.yml">{"manifest"}</a>{"."}</span>
+        <span>{t.format_html("Generated with {} with this {}.", &[
+          html! { <a href="https://faust.rmf.io">{"FAUST"}</a> },
+          html! { <a href="/faust.yml">{t.get("manifest")}</a> },
+        ])}</span>
+        if ctx.languages.len() > 1 {
+          <nav class="languages">
+            {for languages}
+          </nav>
+        }
       </footer>
     }
 }
 
 #[autoprops]
 #[function_component(StaticAppContent)]
-fn static_app_content(route: &Route, data: IArray<u8>) -> Html {
-    let modules: ModuleMap = ciborium::from_reader(data.as_slice()).unwrap();
-    let context = AppContext { modules };
+fn static_app_content(route: &Route, data: IArray<u8>, base: IString) -> Html {
+    let catalog: Catalog = ciborium::from_reader(data.as_slice()).unwrap();
+    let context = AppContext::new(catalog, base.clone());
+    let basename = context.basename();
 
     let history: AnyHistory = {
-        let path = route.to_path();
+        let path = format!("{base}{}", route.to_path());
         let history = MemoryHistory::with_entries(vec![path]);
         history.into()
     };
 
     html! {
       <ContextProvider<AppContext> {context}>
-        <Router {history}>
+        <Router {history} {basename}>
           <Switch<Route> render={switch} />
+          <Footer />
         </Router>
-        <Footer />
       </ContextProvider<AppContext>>
     }
 }
 
 #[autoprops(StaticAppProps)]
 #[function_component(StaticApp)]
-pub fn static_app(route: &Route, data: IArray<u8>) -> HtmlResult {
+pub fn static_app(route: &Route, data: IArray<u8>, #[prop_or_default] base: IString) -> HtmlResult {
     let fallback = html! {<div>{"Loading..."}</div>};
 
     Ok(html! {
       <Suspense {fallback}>
-        <StaticAppContent route={route.clone()} {data}/>
+        <StaticAppContent route={route.clone()} {data} {base} />
       </Suspense>
     })
 }
@@ -39,6 +39,19 @@ pub struct Module {
     pub aliases: HashMap<IString, IString>,
     #[serde(rename = "e")]
     pub eras: IndexMap<IString, Era>,
+    #[serde(rename = "d")]
+    pub descriptions: IndexMap<IString, Description>,
+    #[serde(rename = "B")]
+    pub buildings: IArray<BuildingChain>,
+    #[serde(rename = "r")]
+    pub regions: IArray<Region>,
+    #[serde(rename = "M")]
+    pub map: Option<CampaignMap>,
+    /// What recruiting each unit requires, keyed by unit id.
+    #[serde(rename = "q")]
+    pub requires: HashMap<IString, Requirement>,
+    #[serde(rename = "Q")]
+    pub require_aliases: HashMap<IString, Requirement>,
 }
 
 #[serde_with::apply(
@@ -55,6 +68,8 @@ pub struct Faction {
     pub name: IString,
     #[serde(rename = "b")]
     pub image: IString,
+    #[serde(rename = "c")]
+    pub culture: IString,
     #[serde(rename = "a")]
     pub alias: Option<IString>,
     #[serde(rename = "e")]
@@ -76,6 +91,136 @@ impl Faction {
     }
 }
 
+/// The long and short descriptions of a unit from `export_units.txt`.
+#[derive(PartialEq, Serialize, Deserialize, ImplicitClone, Clone, Debug, Default)]
+pub struct Description {
+    #[serde(rename = "l")]
+    pub long: IString,
+    #[serde(rename = "s")]
+    pub short: IString,
+}
+
+/// A building chain from `export_descr_buildings.txt`, with its levels in order.
+#[serde_with::apply(
+    IArray => #[serde(default, skip_serializing_if = "IArray::is_empty")],
+)]
+#[derive(PartialEq, Serialize, Deserialize, ImplicitClone, Clone, Debug)]
+pub struct BuildingChain {
+    #[serde(rename = "i")]
+    pub id: IString,
+    #[serde(rename = "l")]
+    pub levels: IArray<BuildingLevel>,
+}
+
+#[serde_with::apply(
+    IArray => #[serde(default, skip_serializing_if = "IArray::is_empty")],
+    u32 => #[serde(default, skip_serializing_if = "utils::is_zero_u32")],
+)]
+#[derive(PartialEq, Serialize, Deserialize, ImplicitClone, Clone, Debug)]
+pub struct BuildingLevel {
+    #[serde(rename = "i")]
+    pub id: IString,
+    #[serde(rename = "n")]
+    pub name: IString,
+    #[serde(rename = "d")]
+    pub description: IString,
+    #[serde(rename = "m")]
+    pub min: IString,
+    #[serde(rename = "$")]
+    pub cost: u32,
+    #[serde(rename = "t")]
+    pub turns: u32,
+    #[serde(rename = "u")]
+    pub upgrades: IArray<IString>,
+    #[serde(rename = "c")]
+    pub capabilities: IArray<IString>,
+    #[serde(rename = "r")]
+    pub recruits: IArray<Recruit>,
+}
+
+/// A unit recruited by a building level, and the factions that can recruit it there.
+#[derive(PartialEq, Serialize, Deserialize, ImplicitClone, Clone, Debug)]
+pub struct Recruit {
+    #[serde(rename = "k")]
+    pub unit: IString,
+    #[serde(rename = "n")]
+    pub name: IString,
+    #[serde(rename = "f")]
+    pub factions: IArray<IString>,
+}
+
+#[serde_with::apply(
+    IArray => #[serde(default, skip_serializing_if = "IArray::is_empty")],
+    IndexMap => #[serde(default, skip_serializing_if = "IndexMap::is_empty")],
+)]
+#[derive(PartialEq, Serialize, Deserialize, ImplicitClone, Clone, Debug)]
+pub struct Region {
+    #[serde(rename = "i")]
+    pub id: IString,
+    #[serde(rename = "n")]
+    pub name: IString,
+    /// The name of the region's settlement.
+    #[serde(rename = "s")]
+    pub settlement: IString,
+    #[serde(rename = "h")]
+    pub hidden_resources: IArray<IString>,
+    /// The ids of the units each faction can recruit in the region.
+    #[serde(rename = "u")]
+    pub units: IndexMap<IString, IArray<IString>>,
+    /// The ids of the mercenary pools that cover the region.
+    #[serde(rename = "p")]
+    pub pools: IArray<IString>,
+    /// The outline of the region as an SVG path, in pixels of the campaign map.
+    #[serde(rename = "o", default)]
+    pub outline: IString,
+}
+
+/// The radar map image that region outlines are drawn over, and the size of
+/// `map_regions.tga` that the outlines are measured in.
+#[derive(PartialEq, Serialize, Deserialize, ImplicitClone, Clone, Debug)]
+pub struct CampaignMap {
+    #[serde(rename = "m")]
+    pub image: IString,
+    #[serde(rename = "w")]
+    pub width: u32,
+    #[serde(rename = "h")]
+    pub height: u32,
+}
+
+/// A requirement from `export_descr_buildings.txt`, reduced to the conditions
+/// that can be explored in the browser. The others are kept as `Unknown`.
+#[derive(PartialEq, Serialize, Deserialize, ImplicitClone, Clone, Debug, Default)]
+pub enum Requirement {
+    #[default]
+    #[serde(rename = "_")]
+    None,
+    #[serde(rename = "F")]
+    False,
+    #[serde(rename = "?")]
+    Unknown,
+    #[serde(rename = "h")]
+    HiddenResource(IString),
+    #[serde(rename = "e")]
+    MajorEvent(IString),
+    /// A building chain at the given level or above, counted from 0.
+    #[serde(rename = "b")]
+    BuildingPresent(IString, u32),
+    #[serde(rename = "f")]
+    Factions(IArray<IString>),
+    #[serde(rename = "p")]
+    Port,
+    #[serde(rename = "P")]
+    IsPlayer,
+    #[serde(rename = "a")]
+    Alias(IString),
+    #[serde(rename = "!")]
+    Not(Box<Requirement>),
+    #[serde(rename = "&")]
+    And(Vec<Requirement>),
+    #[serde(rename = "|")]
+    Or(Vec<Requirement>),
+}
+
 #[derive(PartialEq, Serialize, Deserialize, ImplicitClone, Clone, Debug)]
 pub struct Era {
     #[serde(rename = "i")]
@@ -184,6 +329,9 @@ pub struct Unit {
 
     #[serde(rename = "L")]
     pub is_regional: bool,
+    /// The ways the faction recruits the unit, one per building level or rule.
+    #[serde(rename = "q")]
+    pub recruitment: IArray<IString>,
 
     // M2TW
     #[serde(rename = "0")]
@@ -231,6 +379,33 @@ impl UnitClass {
     }
 }
 
+#[derive(Debug, Error)]
+pub struct UnitClassParseError;
+
+impl Display for UnitClassParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "could not parse unit class")
+    }
+}
+
+impl FromStr for UnitClass {
+    type Err = UnitClassParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sword" => Ok(Self::Sword),
+            "spear" => Ok(Self::Spear),
+            "missile" => Ok(Self::Missile),
+            "cavalry" => Ok(Self::Cavalry),
+            "general" => Ok(Self::General),
+            "animal" => Ok(Self::Animal),
+            "artillery" => Ok(Self::Artillery),
+            "ship" => Ok(Self::Ship),
+            _ => Err(UnitClassParseError),
+        }
+    }
+}
+
 impl Display for UnitClass {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -269,6 +444,17 @@ pub enum MountType {
 }
 
 impl MountType {
+    pub fn all() -> [MountType; 6] {
+        [
+            MountType::Foot,
+            MountType::Horse,
+            MountType::Camel,
+            MountType::Elephant,
+            MountType::Chariot,
+            MountType::Other,
+        ]
+    }
+
     pub fn has_mount(&self) -> bool {
         *self != MountType::Foot
     }
@@ -281,6 +467,44 @@ impl MountType {
     }
 }
 
+#[derive(Debug, Error)]
+pub struct MountTypeParseError;
+
+impl Display for MountTypeParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "could not parse mount type")
+    }
+}
+
+impl FromStr for MountType {
+    type Err = MountTypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "foot" => Ok(Self::Foot),
+            "horse" => Ok(Self::Horse),
+            "camel" => Ok(Self::Camel),
+            "elephant" => Ok(Self::Elephant),
+            "chariot" => Ok(Self::Chariot),
+            "other" => Ok(Self::Other),
+            _ => Err(MountTypeParseError),
+        }
+    }
+}
+
+impl Display for MountType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Foot => write!(f, "foot"),
+            Self::Horse => write!(f, "horse"),
+            Self::Camel => write!(f, "camel"),
+            Self::Elephant => write!(f, "elephant"),
+            Self::Chariot => write!(f, "chariot"),
+            Self::Other => write!(f, "other"),
+        }
+    }
+}
+
 #[derive(
     PartialEq,
     Eq,
@@ -314,6 +538,64 @@ pub enum Ability {
     Knight = 16,
 }
 
+impl Ability {
+    pub fn all() -> [Ability; 16] {
+        [
+            Ability::CantHide,
+            Ability::HideImprovedForest,
+            Ability::HideLongGrass,
+            Ability::HideAnywhere,
+            Ability::FrightenFoot,
+            Ability::FrightenMounted,
+            Ability::FrightenAll,
+            Ability::CanRunAmok,
+            Ability::CantabrianCircle,
+            Ability::Command,
+            Ability::Warcry,
+            Ability::PowerCharge,
+            Ability::Chant,
+            Ability::FormedCharge,
+            Ability::Stakes,
+            Ability::Knight,
+        ]
+    }
+}
+
+#[derive(Debug, Error)]
+pub struct AbilityParseError;
+
+impl Display for AbilityParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "could not parse ability")
+    }
+}
+
+impl FromStr for Ability {
+    type Err = AbilityParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cant-hide" => Ok(Self::CantHide),
+            "hide-forest" => Ok(Self::HideImprovedForest),
+            "hide-grass" => Ok(Self::HideLongGrass),
+            "hide-anywhere" => Ok(Self::HideAnywhere),
+            "frighten-foot" => Ok(Self::FrightenFoot),
+            "frighten-mounted" => Ok(Self::FrightenMounted),
+            "frighten-all" => Ok(Self::FrightenAll),
+            "can-run-amok" => Ok(Self::CanRunAmok),
+            "cantabrian-circle" => Ok(Self::CantabrianCircle),
+            "command" => Ok(Self::Command),
+            "warcry" => Ok(Self::Warcry),
+            "power-charge" => Ok(Self::PowerCharge),
+            "chant" => Ok(Self::Chant),
+            "formed-charge" => Ok(Self::FormedCharge),
+            "stakes" => Ok(Self::Stakes),
+            "knight" => Ok(Self::Knight),
+            _ => Err(AbilityParseError),
+        }
+    }
+}
+
 impl Display for Ability {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -395,6 +677,42 @@ pub enum WeaponType {
     Thrown = 4,
 }
 
+impl WeaponType {
+    pub fn all() -> [WeaponType; 5] {
+        [
+            WeaponType::Melee,
+            WeaponType::Spear,
+            WeaponType::Missile,
+            WeaponType::Gunpowder,
+            WeaponType::Thrown,
+        ]
+    }
+}
+
+#[derive(Debug, Error)]
+pub struct WeaponTypeParseError;
+
+impl Display for WeaponTypeParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "could not parse weapon type")
+    }
+}
+
+impl FromStr for WeaponType {
+    type Err = WeaponTypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "melee" => Ok(Self::Melee),
+            "spear" => Ok(Self::Spear),
+            "missile" => Ok(Self::Missile),
+            "gunpowder" => Ok(Self::Gunpowder),
+            "thrown" => Ok(Self::Thrown),
+            _ => Err(WeaponTypeParseError),
+        }
+    }
+}
+
 impl Display for WeaponType {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -597,8 +915,6 @@ pub struct Pool {
     pub regions: IArray<IString>,
     #[serde(rename = "u")]
     pub units: IArray<PoolEntry>,
-    #[serde(rename = "m")]
-    pub map: IString,
 }
 
 #[serde_with::apply(
@@ -723,10 +1039,11 @@ mod utils {
 pub struct Aor {
     #[serde(rename = "n")]
     pub name: IString,
-    #[serde(rename = "m")]
-    pub map: IString,
     #[serde(rename = "r")]
     pub regions: IArray<IString>,
     #[serde(rename = "u")]
     pub units: IArray<IString>,
+    /// The majority religions of the regions, most common first.
+    #[serde(rename = "R", default, skip_serializing_if = "IArray::is_empty")]
+    pub religions: IArray<IString>,
 }
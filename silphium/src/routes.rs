@@ -2,7 +2,10 @@ use implicit_clone::unsync::IString;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
-use crate::components::{FactionPage, MercenaryPage, ModuleList, ModulePage};
+use crate::components::{
+    ArmyPage, BuildingsPage, ComparePage, FactionPage, MapPage, MercenaryPage, ModuleList,
+    ModulePage, UnitPage,
+};
 
 #[derive(Clone, Routable, PartialEq)]
 pub enum Route {
@@ -12,8 +15,22 @@ pub enum Route {
     Module { module: IString },
     #[at("/:module/mercs")]
     Mercenaries { module: IString },
+    #[at("/:module/compare")]
+    Compare { module: IString },
+    #[at("/:module/buildings")]
+    Buildings { module: IString },
+    #[at("/:module/map")]
+    Map { module: IString },
     #[at("/:module/:faction")]
     Faction { module: IString, faction: IString },
+    #[at("/:module/:faction/army")]
+    Army { module: IString, faction: IString },
+    #[at("/:module/:faction/unit/:unit")]
+    Unit {
+        module: IString,
+        faction: IString,
+        unit: IString,
+    },
     #[not_found]
     #[at("/404")]
     NotFound,
@@ -24,14 +41,55 @@ impl Route {
         match self {
             Route::Home => Route::Home,
             Route::Module { .. } => Route::Home,
-            Route::Faction { module, .. } => Route::Module {
+            Route::Faction { module, .. }
+            | Route::Compare { module }
+            | Route::Buildings { module }
+            | Route::Map { module } => Route::Module {
                 module: module.clone(),
             },
+            Route::Army { module, faction } => Route::Faction {
+                module: module.clone(),
+                faction: faction.clone(),
+            },
+            Route::Unit {
+                module, faction, ..
+            } => {
+                if faction == MERCS {
+                    Route::Mercenaries {
+                        module: module.clone(),
+                    }
+                } else {
+                    Route::Faction {
+                        module: module.clone(),
+                        faction: faction.clone(),
+                    }
+                }
+            }
             _ => Route::Home,
         }
     }
+
+    /// The detail page of a unit listed on this page, if it lists units.
+    pub fn unit(&self, key: &IString) -> Option<Self> {
+        match self {
+            Route::Faction { module, faction } => Some(Route::Unit {
+                module: module.clone(),
+                faction: faction.clone(),
+                unit: key.clone(),
+            }),
+            Route::Mercenaries { module } => Some(Route::Unit {
+                module: module.clone(),
+                faction: MERCS.into(),
+                unit: key.clone(),
+            }),
+            _ => None,
+        }
+    }
 }
 
+/// The faction segment of unit routes for units hired as mercenaries.
+pub const MERCS: &str = "mercs";
+
 // TODO bad module/faction/era IDs
 pub fn switch(route: Route) -> Html {
     match route {
@@ -40,9 +98,22 @@ pub fn switch(route: Route) -> Html {
         Route::Mercenaries { module } => {
             html! { <MercenaryPage module_id={module} /> }
         }
+        Route::Compare { module } => html! { <ComparePage module_id={module} /> },
+        Route::Buildings { module } => html! { <BuildingsPage module_id={module} /> },
+        Route::Map { module } => html! { <MapPage module_id={module} /> },
         Route::Faction { module, faction } => {
             html! { <FactionPage module_id={module} faction_id={faction} /> }
         }
+        Route::Army { module, faction } => {
+            html! { <ArmyPage module_id={module} faction_id={faction} /> }
+        }
+        Route::Unit {
+            module,
+            faction,
+            unit,
+        } => {
+            html! { <UnitPage module_id={module} faction_id={faction} unit_key={unit} /> }
+        }
         Route::NotFound => html! { <Redirect<Route> to={Route::Home} /> },
     }
 }
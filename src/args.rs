@@ -1,23 +1,77 @@
-use std::{env, io::Cursor, path::PathBuf};
+use std::{
+    collections::BTreeSet,
+    env,
+    io::Cursor,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
 
-use crate::{parse::Manifest, platform};
-use anyhow::{Context as _, Result};
+use crate::{
+    mod_folder::FileIndex,
+    pack::PackIndex,
+    parse::{Manifest, manifest::SiteManifest},
+    platform,
+};
+use anyhow::{Context as _, Result, bail};
 use clap::Parser;
 
 #[derive(Parser, Debug)]
-#[command(version, about, long_about = None)]
+#[command(version, about, long_about = None, args_conflicts_with_subcommands = true)]
 pub struct Args {
     #[arg(short, long, default_value_t = false, help = "verbose output")]
     pub verbose: bool,
 
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     #[command(flatten)]
     pub generate: GenerateArgs,
 }
 
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    #[command(about = "export the catalog as JSON and CSV files")]
+    Export(ExportArgs),
+    #[command(about = "report balance changes between two versions of a mod")]
+    Diff(DiffArgs),
+    #[command(about = "check the mod data for problems, without generating the site")]
+    Check(CheckArgs),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ExportArgs {
+    #[arg(help = "the manifest files, or a site manifest listing them")]
+    pub manifests: Vec<PathBuf>,
+    #[arg(short, long, help = "where to write the exported files")]
+    pub out_dir: Option<PathBuf>,
+    #[arg(short, long, help = "base game path (for fallbacks)")]
+    pub base_game_path: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DiffArgs {
+    #[arg(help = "the old manifest file, or the mod folder containing faust/faust.yml")]
+    pub old: PathBuf,
+    #[arg(help = "the new manifest file, or the mod folder containing faust/faust.yml")]
+    pub new: PathBuf,
+    #[arg(short, long, help = "write the report as a Markdown (.md) or HTML page")]
+    pub output: Option<PathBuf>,
+    #[arg(short, long, help = "base game path (for fallbacks)")]
+    pub base_game_path: Option<PathBuf>,
+}
+
 #[derive(clap::Args, Debug)]
+pub struct CheckArgs {
+    #[arg(help = "the manifest files, or a site manifest listing them")]
+    pub manifests: Vec<PathBuf>,
+    #[arg(short, long, help = "base game path (for fallbacks)")]
+    pub base_game_path: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug, Clone)]
 pub struct GenerateArgs {
-    #[arg(help = "the manifest file")]
-    pub manifest: Option<PathBuf>,
+    #[arg(help = "the manifest files, or a site manifest listing them")]
+    pub manifests: Vec<PathBuf>,
     #[arg(short, long, help = "where to output the site")]
     pub out_dir: Option<PathBuf>,
     #[arg(short, long, help = "base game path (for fallbacks)")]
@@ -29,6 +83,13 @@ pub struct GenerateArgs {
         help = "serve the site after generation"
     )]
     pub serve: bool,
+    #[arg(
+        short,
+        long,
+        default_value_t = false,
+        help = "serve the site and regenerate it when mod files change"
+    )]
+    pub watch: bool,
     #[arg(long, help = "file to write list of used mod files")]
     pub deps_file: Option<PathBuf>,
 }
@@ -39,59 +100,232 @@ pub struct Config {
     pub src_dir: PathBuf,
     pub out_dir: PathBuf,
     pub fallback_dir: PathBuf,
+    /// The folders mod files are looked up in, in order: the mod itself, the
+    /// roots listed in the manifest, and the base game.
+    pub roots: Vec<PathBuf>,
     pub manifest_dir: PathBuf,
     pub serve: bool,
+    pub watch: bool,
     pub deps_file: Option<PathBuf>,
+    pub deps: Deps,
+    pub files: FileIndex,
+    pub packs: PackIndex,
+}
+
+/// The set of files read while generating, shared by all the configs of a site.
+#[derive(Debug, Clone, Default)]
+pub struct Deps {
+    files: Arc<Mutex<BTreeSet<PathBuf>>>,
+    dirs: Arc<Mutex<BTreeSet<PathBuf>>>,
+}
+
+impl Deps {
+    pub fn add(&self, path: impl Into<PathBuf>) {
+        self.files
+            .lock()
+            .expect("poisoned deps")
+            .insert(path.into());
+    }
+
+    /// Adds a folder where a file was looked for but not found, so that
+    /// adding it later can be noticed.
+    pub fn add_dir(&self, path: impl Into<PathBuf>) {
+        self.dirs.lock().expect("poisoned deps").insert(path.into());
+    }
+
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.files
+            .lock()
+            .expect("poisoned deps")
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    pub fn dirs(&self) -> Vec<PathBuf> {
+        self.dirs
+            .lock()
+            .expect("poisoned deps")
+            .iter()
+            .cloned()
+            .collect()
+    }
 }
 
 impl Config {
-    pub fn get(args: Args) -> Result<Self> {
-        let args = gen_args(args);
-        let manifest_path = args.manifest.unwrap_or_else(|| {
-            env::current_dir()
-                .expect("current directory failed")
-                .join("faust/faust.yml")
-        });
-        let manifest_text = std::fs::read_to_string(&manifest_path)
-            .with_context(|| format!("opening manifest at {}", manifest_path.display()))?;
-        let manifest = Manifest::from_yaml(Cursor::new(&manifest_text))?;
-        let manifest = Manifest {
-            raw: manifest_text,
-            ..manifest
+    pub fn get(args: GenerateArgs) -> Result<Vec<Self>> {
+        Self::from_args(args, "site")
+    }
+
+    pub fn get_export(args: ExportArgs) -> Result<Vec<Self>> {
+        Self::from_args(
+            GenerateArgs {
+                manifests: args.manifests,
+                out_dir: args.out_dir,
+                base_game_path: args.base_game_path,
+                serve: false,
+                watch: false,
+                deps_file: None,
+            },
+            "export",
+        )
+    }
+
+    pub fn get_check(args: CheckArgs) -> Result<Vec<Self>> {
+        Self::from_args(
+            GenerateArgs {
+                manifests: args.manifests,
+                out_dir: None,
+                base_game_path: args.base_game_path,
+                serve: false,
+                watch: false,
+                deps_file: None,
+            },
+            "site",
+        )
+    }
+
+    pub fn get_diff(path: &Path, base_game_path: Option<PathBuf>) -> Result<Vec<Self>> {
+        let manifest = if path.is_dir() {
+            path.join("faust/faust.yml")
+        } else {
+            path.to_path_buf()
+        };
+        Self::from_args(
+            GenerateArgs {
+                manifests: vec![manifest],
+                out_dir: None,
+                base_game_path,
+                serve: false,
+                watch: false,
+                deps_file: None,
+            },
+            "diff",
+        )
+    }
+
+    fn from_args(args: GenerateArgs, default_out_dir: &str) -> Result<Vec<Self>> {
+        let manifest_paths = if args.manifests.is_empty() {
+            vec![
+                env::current_dir()
+                    .expect("current directory failed")
+                    .join("faust/faust.yml"),
+            ]
+        } else {
+            args.manifests.clone()
         };
-        let manifest_dir = manifest_path
-            .parent()
-            .map(|p| p.to_path_buf())
-            .unwrap_or_else(|| env::current_dir().expect("current directory failed"));
-        let src_dir = manifest
-            .dir
+
+        let deps = Deps::default();
+        let files = FileIndex::default();
+        let packs = PackIndex::default();
+        let mut manifests = vec![];
+        for path in manifest_paths {
+            deps.add(&path);
+            let text = std::fs::read_to_string(&path)
+                .with_context(|| format!("opening manifest at {}", path.display()))?;
+            match SiteManifest::from_yaml(Cursor::new(&text))
+                .with_context(|| format!("parsing manifest at {}", path.display()))?
+            {
+                Some(site) => {
+                    let site_dir = parent_dir(&path);
+                    for module in site.modules {
+                        let path = site_dir.join(module);
+                        deps.add(&path);
+                        let text = std::fs::read_to_string(&path)
+                            .with_context(|| format!("opening manifest at {}", path.display()))?;
+                        manifests.push((path, text));
+                    }
+                }
+                None => manifests.push((path, text)),
+            }
+        }
+
+        let out_dir = args
+            .out_dir
             .clone()
-            .map(|d| manifest_dir.join(d))
-            .or_else(|| manifest_dir.parent().map(|p| p.to_path_buf()))
-            .unwrap_or_else(|| manifest_dir.clone());
-        let out_dir = args.out_dir.unwrap_or_else(|| manifest_dir.join("site"));
-        let fallback_dir = args
-            .base_game_path
-            .or_else(|| src_dir.parent().map(|p| p.to_path_buf()))
-            .unwrap_or_else(|| "..".into());
+            .unwrap_or_else(|| parent_dir(&manifests[0].0).join(default_out_dir));
 
         if let Some(deps_file) = &args.deps_file {
-            std::fs::write(deps_file, format!("{}\n", manifest_path.display()))
-                .context("creating deps file")?;
+            let paths = manifests.iter().fold(String::new(), |s, (path, _)| {
+                s + &format!("{}\n", path.display())
+            });
+            std::fs::write(deps_file, paths).context("creating deps file")?;
         }
 
-        Ok(Self {
-            manifest,
-            out_dir,
-            src_dir,
-            fallback_dir,
-            manifest_dir,
-            serve: args.serve,
-            deps_file: args.deps_file,
-        })
+        let mut cfgs: Vec<Self> = vec![];
+        for (manifest_path, manifest_text) in manifests {
+            let manifest = Manifest::from_yaml(Cursor::new(&manifest_text))
+                .with_context(|| format!("parsing manifest at {}", manifest_path.display()))?;
+            if cfgs.iter().any(|c| c.manifest.id == manifest.id) {
+                bail!("duplicate module id {}", manifest.id);
+            }
+            let manifest = Manifest {
+                raw: manifest_text,
+                ..manifest
+            };
+            let manifest_dir = parent_dir(&manifest_path);
+            let src_dir = manifest
+                .dir
+                .clone()
+                .map(|d| manifest_dir.join(d))
+                .or_else(|| manifest_dir.parent().map(|p| p.to_path_buf()))
+                .unwrap_or_else(|| manifest_dir.clone());
+            let fallback_dir = args
+                .base_game_path
+                .clone()
+                .or_else(|| src_dir.parent().map(|p| p.to_path_buf()))
+                .unwrap_or_else(|| "..".into());
+            let roots = [src_dir.clone()]
+                .into_iter()
+                .chain(manifest.roots.iter().map(|r| manifest_dir.join(r)))
+                .chain([fallback_dir.clone()])
+                .collect();
+
+            cfgs.push(Self {
+                manifest,
+                out_dir: out_dir.clone(),
+                src_dir,
+                fallback_dir,
+                roots,
+                manifest_dir,
+                serve: args.serve || args.watch,
+                watch: args.watch,
+                deps_file: args.deps_file.clone(),
+                deps: deps.clone(),
+                files: files.clone(),
+                packs: packs.clone(),
+            });
+        }
+
+        // The first language is at the root of the site and the others under
+        // their id, which must not be taken by a module
+        let mut first_language = None;
+        for cfg in cfgs.iter() {
+            if let Some(first) = cfg.manifest.languages.keys().next() {
+                match first_language {
+                    Some(site) if site != first => bail!(
+                        "module {} has {first} as first language instead of {site}",
+                        cfg.manifest.id
+                    ),
+                    _ => first_language = Some(first),
+                }
+            }
+            for language in cfg.manifest.languages.keys() {
+                if cfgs.iter().any(|c| &c.manifest.id == language) {
+                    bail!("language {language} has the same id as a module");
+                }
+            }
+        }
+        Ok(cfgs)
     }
 }
 
+fn parent_dir(path: &Path) -> PathBuf {
+    path.parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_else(|| env::current_dir().expect("current directory failed"))
+}
+
 pub fn gen_args(args: Args) -> GenerateArgs {
     platform::prepare_generation_arguments(args)
 }
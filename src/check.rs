@@ -0,0 +1,39 @@
+use std::time::Instant;
+
+use anyhow::{Result, bail};
+use console::style;
+use indicatif::HumanDuration;
+
+use crate::{
+    args::Config,
+    parse,
+    utils::{LOOKING_GLASS, SPARKLE},
+};
+
+pub async fn check(cfgs: &[Config]) -> Result<()> {
+    let started = Instant::now();
+    let diagnostics = parse::check_site(cfgs).await?;
+    println!(
+        "{LOOKING_GLASS}{}",
+        style(format!(
+            "checked mod folder in {}",
+            HumanDuration(started.elapsed())
+        ))
+        .green()
+    );
+
+    if diagnostics.is_empty() {
+        println!("{SPARKLE}{}", style("No problems found").bold());
+        return Ok(());
+    }
+    for d in diagnostics.iter() {
+        println!(
+            "{}:{}: {} {}",
+            d.path.display(),
+            d.line,
+            style("warning:").yellow().bold(),
+            d.message
+        );
+    }
+    bail!("found {} problems", diagnostics.len())
+}
@@ -0,0 +1,442 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write as _,
+    path::Path,
+};
+
+use anyhow::{Context as _, Result};
+use askama::Template as _;
+use console::style;
+use silphium::{ModuleMap, model};
+
+use crate::{
+    export::{POOL_COLUMNS, UNIT_COLUMNS, pool_row, unit_row},
+    render::DiffHtml,
+    utils::{PAPER, write_file},
+};
+
+/// Balance changes between two parsed versions of a site.
+pub struct Report {
+    pub modules: Vec<ModuleDiff>,
+    pub added_modules: Vec<String>,
+    pub removed_modules: Vec<String>,
+}
+
+pub struct ModuleDiff {
+    pub name: String,
+    pub added_factions: Vec<String>,
+    pub removed_factions: Vec<String>,
+    pub factions: Vec<FactionDiff>,
+    pub added_pools: Vec<String>,
+    pub removed_pools: Vec<String>,
+    pub pools: Vec<PoolDiff>,
+}
+
+pub struct FactionDiff {
+    pub name: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<UnitDiff>,
+}
+
+pub struct PoolDiff {
+    pub name: String,
+    pub added_regions: Vec<String>,
+    pub removed_regions: Vec<String>,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<UnitDiff>,
+}
+
+pub struct UnitDiff {
+    pub name: String,
+    pub changes: Vec<Change>,
+}
+
+pub struct Change {
+    pub field: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+impl Report {
+    pub fn new(old: &ModuleMap, new: &ModuleMap) -> Self {
+        // a single module on each side is compared even if its id was changed
+        let renamed = old.len() == 1 && new.len() == 1;
+        let pairs: Vec<_> = match (old.first(), new.first()) {
+            (Some((_, o)), Some((_, n))) if renamed => vec![(o, n)],
+            _ => new
+                .values()
+                .filter_map(|n| old.get(&n.id).map(|o| (o, n)))
+                .collect(),
+        };
+        Self {
+            modules: pairs
+                .into_iter()
+                .map(|(o, n)| ModuleDiff::new(o, n))
+                .collect(),
+            added_modules: new
+                .values()
+                .filter(|m| !renamed && !old.contains_key(&m.id))
+                .map(|m| m.name.to_string())
+                .collect(),
+            removed_modules: old
+                .values()
+                .filter(|m| !renamed && !new.contains_key(&m.id))
+                .map(|m| m.name.to_string())
+                .collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_modules.is_empty()
+            && self.removed_modules.is_empty()
+            && self.modules.iter().all(ModuleDiff::is_empty)
+    }
+
+    pub fn print(&self) {
+        for name in &self.added_modules {
+            println!("{} module {}", style("+").green(), style(name).bold());
+        }
+        for name in &self.removed_modules {
+            println!("{} module {}", style("-").red(), style(name).bold());
+        }
+        for module in self.modules.iter().filter(|m| !m.is_empty()) {
+            println!("{}", style(&module.name).bold().underlined());
+            for name in &module.added_factions {
+                println!("  {} faction {}", style("+").green(), style(name).bold());
+            }
+            for name in &module.removed_factions {
+                println!("  {} faction {}", style("-").red(), style(name).bold());
+            }
+            for faction in module.factions.iter().filter(|f| !f.is_empty()) {
+                println!("  {}", style(&faction.name).bold());
+                print_units(&faction.added, &faction.removed, &faction.changed);
+            }
+            for name in &module.added_pools {
+                println!("  {} pool {}", style("+").green(), style(name).bold());
+            }
+            for name in &module.removed_pools {
+                println!("  {} pool {}", style("-").red(), style(name).bold());
+            }
+            for pool in module.pools.iter().filter(|p| !p.is_empty()) {
+                println!("  {}", style(format!("{} (mercenaries)", pool.name)).bold());
+                for region in &pool.added_regions {
+                    println!("    {} region {region}", style("+").green());
+                }
+                for region in &pool.removed_regions {
+                    println!("    {} region {region}", style("-").red());
+                }
+                print_units(&pool.added, &pool.removed, &pool.changed);
+            }
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("# Balance changes\n");
+        if !self.added_modules.is_empty() || !self.removed_modules.is_empty() {
+            md.push('\n');
+            md_list(&mut md, "Added modules", &self.added_modules);
+            md_list(&mut md, "Removed modules", &self.removed_modules);
+        }
+        for module in self.modules.iter().filter(|m| !m.is_empty()) {
+            let _ = writeln!(md, "\n## {}\n", md_escape(&module.name));
+            md_list(&mut md, "Added factions", &module.added_factions);
+            md_list(&mut md, "Removed factions", &module.removed_factions);
+            md_list(&mut md, "Added mercenary pools", &module.added_pools);
+            md_list(&mut md, "Removed mercenary pools", &module.removed_pools);
+            for faction in module.factions.iter().filter(|f| !f.is_empty()) {
+                let _ = writeln!(md, "\n### {}\n", md_escape(&faction.name));
+                md_list(&mut md, "Added units", &faction.added);
+                md_list(&mut md, "Removed units", &faction.removed);
+                md_table(&mut md, &faction.changed);
+            }
+            for pool in module.pools.iter().filter(|p| !p.is_empty()) {
+                let _ = writeln!(md, "\n### {} (mercenaries)\n", md_escape(&pool.name));
+                md_list(&mut md, "Added regions", &pool.added_regions);
+                md_list(&mut md, "Removed regions", &pool.removed_regions);
+                md_list(&mut md, "Added units", &pool.added);
+                md_list(&mut md, "Removed units", &pool.removed);
+                md_table(&mut md, &pool.changed);
+            }
+        }
+        md
+    }
+
+    pub async fn write(&self, path: &Path) -> Result<()> {
+        let contents = if path.extension().is_some_and(|e| e == "md") {
+            self.to_markdown()
+        } else {
+            DiffHtml { report: self }
+                .render()
+                .context("rendering the diff page")?
+        };
+        write_file(path, contents)
+            .await
+            .with_context(|| format!("writing {}", path.display()))?;
+        println!("{PAPER}Report written to {}", style(path.display()).bold());
+        Ok(())
+    }
+}
+
+impl ModuleDiff {
+    fn new(old: &model::Module, new: &model::Module) -> Self {
+        let factions = new
+            .factions
+            .values()
+            .filter_map(|n| {
+                old.factions.get(&n.id).map(|o| FactionDiff {
+                    name: display_name(&n.name),
+                    ..diff_units(
+                        &o.roster.iter().collect::<Vec<_>>(),
+                        &n.roster.iter().collect::<Vec<_>>(),
+                    )
+                })
+            })
+            .collect();
+
+        let old_pools: HashMap<_, _> = old.pools.iter().map(|p| (p.id.clone(), p)).collect();
+        let new_pools: HashMap<_, _> = new.pools.iter().map(|p| (p.id.clone(), p)).collect();
+        let pools = new
+            .pools
+            .iter()
+            .filter_map(|n| old_pools.get(&n.id).map(|o| PoolDiff::new(o, &n)))
+            .collect();
+
+        Self {
+            name: display_name(&new.name),
+            added_factions: new
+                .factions
+                .values()
+                .filter(|f| !old.factions.contains_key(&f.id))
+                .map(|f| display_name(&f.name))
+                .collect(),
+            removed_factions: old
+                .factions
+                .values()
+                .filter(|f| !new.factions.contains_key(&f.id))
+                .map(|f| display_name(&f.name))
+                .collect(),
+            factions,
+            added_pools: new
+                .pools
+                .iter()
+                .filter(|p| !old_pools.contains_key(&p.id))
+                .map(|p| display_name(&p.name))
+                .collect(),
+            removed_pools: old
+                .pools
+                .iter()
+                .filter(|p| !new_pools.contains_key(&p.id))
+                .map(|p| display_name(&p.name))
+                .collect(),
+            pools,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_factions.is_empty()
+            && self.removed_factions.is_empty()
+            && self.added_pools.is_empty()
+            && self.removed_pools.is_empty()
+            && self.factions.iter().all(FactionDiff::is_empty)
+            && self.pools.iter().all(PoolDiff::is_empty)
+    }
+}
+
+impl FactionDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl PoolDiff {
+    fn new(old: &model::Pool, new: &model::Pool) -> Self {
+        // a unit may appear more than once in a pool with different restrictions
+        let key = |e: &model::PoolEntry| format!("{}:{}", e.unit.id, restrict_key(e));
+        let old_entries: HashMap<_, _> = old.units.iter().map(|e| (key(&e), e)).collect();
+        let new_keys: Vec<_> = new.units.iter().map(|e| key(&e)).collect();
+
+        let mut added = vec![];
+        let mut changed = vec![];
+        for (entry, key) in new.units.iter().zip(new_keys.iter()) {
+            match old_entries.get(key) {
+                None => added.push(unit_name(&entry.unit)),
+                Some(o) => {
+                    let old_row = pool_row(old, o);
+                    let new_row = pool_row(new, &entry);
+                    // the pool id and name are the same for every entry
+                    let mut changes = diff_cells(&POOL_COLUMNS[2..], &old_row[2..], &new_row[2..]);
+                    changes.extend(diff_unit(&o.unit, &entry.unit));
+                    if !changes.is_empty() {
+                        changed.push(UnitDiff {
+                            name: unit_name(&entry.unit),
+                            changes,
+                        });
+                    }
+                }
+            }
+        }
+        let removed = old
+            .units
+            .iter()
+            .filter(|e| !new_keys.contains(&key(e)))
+            .map(|e| unit_name(&e.unit))
+            .collect();
+
+        Self {
+            name: display_name(&new.name),
+            added_regions: new
+                .regions
+                .iter()
+                .filter(|r| !old.regions.contains(r))
+                .map(|r| r.to_string())
+                .collect(),
+            removed_regions: old
+                .regions
+                .iter()
+                .filter(|r| !new.regions.contains(r))
+                .map(|r| r.to_string())
+                .collect(),
+            added,
+            removed,
+            changed,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_regions.is_empty()
+            && self.removed_regions.is_empty()
+            && self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
+    }
+}
+
+fn restrict_key(entry: &model::PoolEntry) -> String {
+    entry
+        .restrict
+        .iter()
+        .map(|r| r.to_string())
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn diff_units(old: &[model::Unit], new: &[model::Unit]) -> FactionDiff {
+    let old_units: HashMap<_, _> = old.iter().map(|u| (u.id.clone(), u)).collect();
+    let new_units: HashMap<_, _> = new.iter().map(|u| (u.id.clone(), u)).collect();
+
+    let mut seen = HashSet::new();
+    let mut added = vec![];
+    let mut changed = vec![];
+    for unit in new.iter().filter(|u| seen.insert(u.id.clone())) {
+        match old_units.get(&unit.id) {
+            None => added.push(unit_name(unit)),
+            Some(o) => {
+                let changes = diff_unit(o, unit);
+                if !changes.is_empty() {
+                    changed.push(UnitDiff {
+                        name: unit_name(unit),
+                        changes,
+                    });
+                }
+            }
+        }
+    }
+    let mut seen = HashSet::new();
+    let removed = old
+        .iter()
+        .filter(|u| seen.insert(u.id.clone()) && !new_units.contains_key(&u.id))
+        .map(unit_name)
+        .collect();
+
+    FactionDiff {
+        name: String::new(),
+        added,
+        removed,
+        changed,
+    }
+}
+
+fn diff_unit(old: &model::Unit, new: &model::Unit) -> Vec<Change> {
+    diff_cells(&UNIT_COLUMNS, &unit_row(old), &unit_row(new))
+}
+
+fn diff_cells(columns: &[&'static str], old: &[String], new: &[String]) -> Vec<Change> {
+    columns
+        .iter()
+        .zip(old.iter().zip(new.iter()))
+        .filter(|(_, (o, n))| o != n)
+        .map(|(field, (o, n))| Change {
+            field,
+            old: o.clone(),
+            new: n.clone(),
+        })
+        .collect()
+}
+
+fn display_name(name: &str) -> String {
+    name.replace('\n', " ")
+}
+
+fn unit_name(unit: &model::Unit) -> String {
+    format!("{} ({})", display_name(&unit.name), unit.id)
+}
+
+fn print_units(added: &[String], removed: &[String], changed: &[UnitDiff]) {
+    for name in added {
+        println!("    {} {name}", style("+").green());
+    }
+    for name in removed {
+        println!("    {} {name}", style("-").red());
+    }
+    for unit in changed {
+        println!("    {} {}", style("~").yellow(), unit.name);
+        for change in &unit.changes {
+            println!(
+                "        {}: {} → {}",
+                change.field,
+                style(&change.old).red(),
+                style(&change.new).green()
+            );
+        }
+    }
+}
+
+fn md_list(md: &mut String, title: &str, items: &[String]) {
+    if !items.is_empty() {
+        let _ = writeln!(md, "**{title}:**\n");
+        for item in items {
+            let _ = writeln!(md, "- {}", md_escape(item));
+        }
+        md.push('\n');
+    }
+}
+
+fn md_table(md: &mut String, units: &[UnitDiff]) {
+    if !units.is_empty() {
+        md.push_str("| Unit | Field | Old | New |\n|---|---|---|---|\n");
+        for unit in units {
+            for (i, change) in unit.changes.iter().enumerate() {
+                let name = if i == 0 {
+                    md_escape(&unit.name)
+                } else {
+                    String::new()
+                };
+                let _ = writeln!(
+                    md,
+                    "| {name} | {} | {} | {} |",
+                    change.field,
+                    md_escape(&change.old),
+                    md_escape(&change.new)
+                );
+            }
+        }
+    }
+}
+
+fn md_escape(s: &str) -> String {
+    s.replace('|', "\\|")
+        .replace('*', "\\*")
+        .replace('_', "\\_")
+}
@@ -0,0 +1,561 @@
+use std::{fmt::Display, path::Path, time::Instant};
+
+use anyhow::{Context as _, Result};
+use console::style;
+use indicatif::{HumanDuration, MultiProgress, ProgressBar};
+use serde::Serialize;
+use silphium::{ModuleMap, model};
+use tracing::info;
+
+use crate::{
+    args::Config,
+    utils::{PACKAGE, PAPER, SPARKLE, progress_style, write_file},
+};
+
+pub async fn export(cfgs: &[Config], modules: &ModuleMap) -> Result<()> {
+    let started = Instant::now();
+    let out_dir = &cfgs[0].out_dir;
+
+    let m = MultiProgress::new();
+    let pb = m.add(ProgressBar::new_spinner());
+    pb.set_style(progress_style());
+    for module in modules.values() {
+        let json_path = out_dir.join(format!("{}.json", module.id));
+        pb.tick();
+        pb.set_message(format!("{PAPER}writing {}", json_path.display()));
+        let json = serde_json::to_string_pretty(&Module::from(module))
+            .with_context(|| format!("serializing module {}", module.id))?;
+        write_file(&json_path, json)
+            .await
+            .with_context(|| format!("writing {}", json_path.display()))?;
+        info!("exported {}", json_path.display());
+
+        let module_dir = out_dir.join(module.id.as_str());
+        for faction in module.factions.values() {
+            let csv_path = module_dir.join(format!("{}.csv", faction.id));
+            pb.tick();
+            pb.set_message(format!("{PAPER}writing {}", csv_path.display()));
+            write_csv(
+                &csv_path,
+                &UNIT_COLUMNS,
+                faction.roster.iter().map(|u| unit_row(&u)),
+            )
+            .await?;
+        }
+
+        if module.pools.len() > 0 {
+            let csv_path = module_dir.join("mercs.csv");
+            pb.tick();
+            pb.set_message(format!("{PAPER}writing {}", csv_path.display()));
+            let columns: Vec<_> = POOL_COLUMNS.iter().chain(UNIT_COLUMNS.iter()).collect();
+            let rows = module.pools.iter().flat_map(|p| {
+                p.units
+                    .iter()
+                    .map(|e| {
+                        let mut row = pool_row(&p, &e);
+                        row.extend(unit_row(&e.unit));
+                        row
+                    })
+                    .collect::<Vec<_>>()
+            });
+            write_csv(&csv_path, &columns, rows).await?;
+        }
+    }
+    pb.finish_with_message(format!("{PAPER}exported {} modules", modules.len()));
+    let _ = m.clear();
+
+    println!(
+        "{SPARKLE}{}",
+        style(format!("Done in {}", HumanDuration(started.elapsed()))).bold()
+    );
+    println!(
+        "{PACKAGE}Exported files available at {}",
+        style(out_dir.display()).bold(),
+    );
+    Ok(())
+}
+
+async fn write_csv(
+    path: &Path,
+    columns: &[impl Display],
+    rows: impl IntoIterator<Item = Vec<String>>,
+) -> Result<()> {
+    let mut csv = csv_line(columns.iter().map(ToString::to_string));
+    for row in rows {
+        csv.push_str(&csv_line(row));
+    }
+    write_file(path, csv)
+        .await
+        .with_context(|| format!("writing {}", path.display()))?;
+    info!("exported {}", path.display());
+    Ok(())
+}
+
+fn csv_line(cells: impl IntoIterator<Item = String>) -> String {
+    let mut line = cells
+        .into_iter()
+        .map(|c| {
+            if c.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", c.replace('"', "\"\""))
+            } else {
+                c
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+pub const POOL_COLUMNS: [&str; 8] = [
+    "pool",
+    "pool_name",
+    "experience",
+    "replenish_min",
+    "replenish_max",
+    "max",
+    "initial",
+    "restrict",
+];
+
+pub fn pool_row(pool: &model::Pool, entry: &model::PoolEntry) -> Vec<String> {
+    vec![
+        pool.id.to_string(),
+        pool.name.to_string(),
+        entry.exp.to_string(),
+        entry.replenish.min.to_string(),
+        entry.replenish.max.to_string(),
+        entry.max.to_string(),
+        entry.initial.to_string(),
+        join(entry.restrict.iter()),
+    ]
+}
+
+pub const UNIT_COLUMNS: [&str; 69] = [
+    "id",
+    "key",
+    "name",
+    "class",
+    "soldiers",
+    "officers",
+    "mount",
+    "formations",
+    "hp",
+    "hp_mount",
+    "primary_weapon",
+    "primary_attack",
+    "primary_charge",
+    "primary_missile",
+    "primary_range",
+    "primary_ammo",
+    "primary_lethality",
+    "primary_armor_piercing",
+    "primary_body_piercing",
+    "primary_pre_charge",
+    "primary_launching",
+    "primary_area",
+    "primary_fire",
+    "primary_spear_bonus",
+    "secondary_weapon",
+    "secondary_attack",
+    "secondary_charge",
+    "secondary_missile",
+    "secondary_range",
+    "secondary_ammo",
+    "secondary_lethality",
+    "secondary_armor_piercing",
+    "secondary_body_piercing",
+    "secondary_pre_charge",
+    "secondary_launching",
+    "secondary_area",
+    "secondary_fire",
+    "secondary_spear_bonus",
+    "armor",
+    "skill",
+    "shield",
+    "defense",
+    "mount_armor",
+    "mount_skill",
+    "mount_shield",
+    "mount_defense",
+    "heat",
+    "scrub",
+    "sand",
+    "forest",
+    "snow",
+    "morale",
+    "discipline",
+    "turns",
+    "cost",
+    "upkeep",
+    "stamina",
+    "inexhaustible",
+    "infinite_ammo",
+    "abilities",
+    "horde",
+    "general",
+    "mercenary",
+    "militia",
+    "unique",
+    "eras",
+    "tech_level",
+    "speed",
+    "regional",
+];
+
+pub fn unit_row(unit: &model::Unit) -> Vec<String> {
+    let mut row = vec![
+        unit.id.to_string(),
+        unit.key.to_string(),
+        unit.name.replace('\n', " "),
+        unit.class.to_string(),
+        unit.soldiers.to_string(),
+        unit.officers.to_string(),
+        unit.mount.to_string(),
+        join(unit.formations.iter()),
+        unit.hp.to_string(),
+        unit.hp_mount.to_string(),
+    ];
+    row.extend(weapon_cells(&unit.primary_weapon));
+    row.extend(weapon_cells(&unit.secondary_weapon));
+    row.extend(defense_cells(&unit.defense));
+    row.extend(defense_cells(&unit.defense_mount));
+    row.extend([
+        unit.heat.to_string(),
+        unit.ground_bonus.scrub.to_string(),
+        unit.ground_bonus.sand.to_string(),
+        unit.ground_bonus.forest.to_string(),
+        unit.ground_bonus.snow.to_string(),
+        unit.morale.to_string(),
+        unit.discipline.to_string(),
+        unit.turns.to_string(),
+        unit.cost.to_string(),
+        unit.upkeep.to_string(),
+        unit.stamina.to_string(),
+        unit.inexhaustible.to_string(),
+        unit.infinite_ammo.to_string(),
+        join(unit.abilities.iter()),
+        unit.horde.to_string(),
+        unit.general.to_string(),
+        unit.is_mercenary.to_string(),
+        unit.is_militia.to_string(),
+        unit.is_unique.to_string(),
+        join(unit.eras.iter()),
+        unit.tech_level.to_string(),
+        unit.move_speed.map(|s| s.to_string()).unwrap_or_default(),
+        unit.is_regional.to_string(),
+    ]);
+    row
+}
+
+fn weapon_cells(weapon: &Option<model::Weapon>) -> Vec<String> {
+    match weapon {
+        Some(w) => vec![
+            w.class.to_string(),
+            w.factor.to_string(),
+            w.charge.to_string(),
+            w.is_missile.to_string(),
+            w.range.to_string(),
+            w.ammo.to_string(),
+            w.lethality.to_string(),
+            w.armor_piercing.to_string(),
+            w.body_piercing.to_string(),
+            w.pre_charge.to_string(),
+            w.launching.to_string(),
+            w.area.to_string(),
+            w.fire.to_string(),
+            w.spear_bonus.to_string(),
+        ],
+        None => vec![String::new(); 14],
+    }
+}
+
+fn defense_cells(defense: &model::Defense) -> [String; 4] {
+    [
+        defense.armor.to_string(),
+        defense.skill.to_string(),
+        defense.shield.to_string(),
+        defense.total().to_string(),
+    ]
+}
+
+fn join<T: Display>(items: impl IntoIterator<Item = T>) -> String {
+    items
+        .into_iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+// The catalog model uses single-letter field names to keep mods.cbor small;
+// these mirror it with full names for other tools to consume.
+
+#[derive(Serialize)]
+pub struct Module {
+    pub id: String,
+    pub name: String,
+    pub factions: Vec<Faction>,
+    pub pools: Vec<Pool>,
+    pub eras: Vec<Era>,
+}
+
+impl From<&model::Module> for Module {
+    fn from(m: &model::Module) -> Self {
+        Self {
+            id: m.id.to_string(),
+            name: m.name.to_string(),
+            factions: m.factions.values().map(Faction::from).collect(),
+            pools: m.pools.iter().map(|p| Pool::from(&p)).collect(),
+            eras: m
+                .eras
+                .values()
+                .map(|e| Era {
+                    id: e.id.to_string(),
+                    name: e.name.to_string(),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Era {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Serialize)]
+pub struct Faction {
+    pub id: String,
+    pub name: String,
+    pub alias: Option<String>,
+    pub eras: Vec<String>,
+    pub is_horde: bool,
+    pub roster: Vec<Unit>,
+    pub areas_of_recruitment: Vec<Aor>,
+}
+
+impl From<&model::Faction> for Faction {
+    fn from(f: &model::Faction) -> Self {
+        Self {
+            id: f.id.to_string(),
+            name: f.name.to_string(),
+            alias: f.alias.as_ref().map(ToString::to_string),
+            eras: strings(f.eras.iter()),
+            is_horde: f.is_horde,
+            roster: f.roster.iter().map(|u| Unit::from(&u)).collect(),
+            areas_of_recruitment: f
+                .aors
+                .iter()
+                .map(|a| Aor {
+                    name: a.name.to_string(),
+                    regions: strings(a.regions.iter()),
+                    units: strings(a.units.iter()),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Aor {
+    pub name: String,
+    pub regions: Vec<String>,
+    pub units: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct Pool {
+    pub id: String,
+    pub name: String,
+    pub regions: Vec<String>,
+    pub units: Vec<PoolEntry>,
+}
+
+impl From<&model::Pool> for Pool {
+    fn from(p: &model::Pool) -> Self {
+        Self {
+            id: p.id.to_string(),
+            name: p.name.to_string(),
+            regions: strings(p.regions.iter()),
+            units: p
+                .units
+                .iter()
+                .map(|e| PoolEntry {
+                    unit: Unit::from(&e.unit),
+                    experience: e.exp,
+                    replenish_min: e.replenish.min,
+                    replenish_max: e.replenish.max,
+                    max: e.max,
+                    initial: e.initial,
+                    restrict: strings(e.restrict.iter()),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PoolEntry {
+    pub unit: Unit,
+    pub experience: u32,
+    pub replenish_min: f64,
+    pub replenish_max: f64,
+    pub max: u32,
+    pub initial: u32,
+    pub restrict: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct Unit {
+    pub id: String,
+    pub key: String,
+    pub name: String,
+    pub class: String,
+    pub soldiers: u32,
+    pub officers: u32,
+    pub mount: String,
+    pub formations: Vec<String>,
+    pub hp: u32,
+    pub hp_mount: u32,
+    pub primary_weapon: Option<Weapon>,
+    pub secondary_weapon: Option<Weapon>,
+    pub defense: Defense,
+    pub defense_mount: Defense,
+    pub heat: i32,
+    pub ground_bonus: GroundBonus,
+    pub morale: u32,
+    pub discipline: String,
+    pub turns: u32,
+    pub cost: u32,
+    pub upkeep: u32,
+    pub stamina: u32,
+    pub inexhaustible: bool,
+    pub infinite_ammo: bool,
+    pub abilities: Vec<String>,
+    pub horde: bool,
+    pub general: bool,
+    pub is_mercenary: bool,
+    pub eras: Vec<String>,
+    pub tech_level: u32,
+    pub move_speed: Option<u32>,
+    pub is_regional: bool,
+    pub is_militia: bool,
+    pub is_unique: bool,
+}
+
+impl From<&model::Unit> for Unit {
+    fn from(u: &model::Unit) -> Self {
+        Self {
+            id: u.id.to_string(),
+            key: u.key.to_string(),
+            name: u.name.to_string(),
+            class: u.class.to_string(),
+            soldiers: u.soldiers,
+            officers: u.officers,
+            mount: u.mount.to_string(),
+            formations: strings(u.formations.iter()),
+            hp: u.hp,
+            hp_mount: u.hp_mount,
+            primary_weapon: u.primary_weapon.as_ref().map(Weapon::from),
+            secondary_weapon: u.secondary_weapon.as_ref().map(Weapon::from),
+            defense: Defense::from(&u.defense),
+            defense_mount: Defense::from(&u.defense_mount),
+            heat: u.heat,
+            ground_bonus: GroundBonus {
+                scrub: u.ground_bonus.scrub,
+                sand: u.ground_bonus.sand,
+                forest: u.ground_bonus.forest,
+                snow: u.ground_bonus.snow,
+            },
+            morale: u.morale,
+            discipline: u.discipline.to_string(),
+            turns: u.turns,
+            cost: u.cost,
+            upkeep: u.upkeep,
+            stamina: u.stamina,
+            inexhaustible: u.inexhaustible,
+            infinite_ammo: u.infinite_ammo,
+            abilities: strings(u.abilities.iter()),
+            horde: u.horde,
+            general: u.general,
+            is_mercenary: u.is_mercenary,
+            eras: strings(u.eras.iter()),
+            tech_level: u.tech_level,
+            move_speed: u.move_speed,
+            is_regional: u.is_regional,
+            is_militia: u.is_militia,
+            is_unique: u.is_unique,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Weapon {
+    pub class: String,
+    pub attack: u32,
+    pub is_missile: bool,
+    pub charge: u32,
+    pub range: u32,
+    pub ammo: u32,
+    pub lethality: f64,
+    pub armor_piercing: bool,
+    pub body_piercing: bool,
+    pub pre_charge: bool,
+    pub launching: bool,
+    pub area: bool,
+    pub fire: bool,
+    pub spear_bonus: u32,
+}
+
+impl From<&model::Weapon> for Weapon {
+    fn from(w: &model::Weapon) -> Self {
+        Self {
+            class: w.class.to_string(),
+            attack: w.factor,
+            is_missile: w.is_missile,
+            charge: w.charge,
+            range: w.range,
+            ammo: w.ammo,
+            lethality: w.lethality,
+            armor_piercing: w.armor_piercing,
+            body_piercing: w.body_piercing,
+            pre_charge: w.pre_charge,
+            launching: w.launching,
+            area: w.area,
+            fire: w.fire,
+            spear_bonus: w.spear_bonus,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct Defense {
+    pub armor: u32,
+    pub skill: u32,
+    pub shield: u32,
+    pub total: u32,
+}
+
+impl From<&model::Defense> for Defense {
+    fn from(d: &model::Defense) -> Self {
+        Self {
+            armor: d.armor,
+            skill: d.skill,
+            shield: d.shield,
+            total: d.total(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct GroundBonus {
+    pub scrub: i32,
+    pub sand: i32,
+    pub forest: i32,
+    pub snow: i32,
+}
+
+fn strings<T: Display>(items: impl IntoIterator<Item = T>) -> Vec<String> {
+    items.into_iter().map(|i| i.to_string()).collect()
+}
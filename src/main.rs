@@ -16,19 +16,25 @@ use indicatif::HumanDuration;
 use tracing_subscriber::{filter, fmt::time::ChronoLocal, prelude::*};
 
 use crate::{
-    args::{Args, Config},
+    args::{Args, Command, Config, DiffArgs, GenerateArgs, gen_args},
+    diff::Report,
     render::Renderer,
     serve::serve,
     utils::{LINK, LOOKING_GLASS, PACKAGE, SPARKLE},
 };
 
 mod args;
+mod check;
+mod diff;
+mod export;
 mod mod_folder;
+mod pack;
 mod parse;
 mod platform;
 mod render;
 mod serve;
 mod utils;
+mod watch;
 
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> Result<()> {
@@ -54,15 +60,21 @@ fn setup_tracing(args: &Args) -> Result<()> {
 }
 
 async fn run() -> Result<()> {
-    let args = Args::parse();
-    let started = Instant::now();
+    let mut args = Args::parse();
 
     setup_tracing(&args)?;
 
-    let cfg = Config::get(args)?;
+    match args.command.take() {
+        Some(Command::Export(export)) => run_export(Config::get_export(export)?).await,
+        Some(Command::Diff(diff)) => run_diff(diff).await,
+        Some(Command::Check(check)) => check::check(&Config::get_check(check)?).await,
+        None => run_generate(gen_args(args)).await,
+    }
+}
 
+async fn run_export(cfgs: Vec<Config>) -> Result<()> {
     let step = Instant::now();
-    let (modules, extra) = parse::parse_folder(&cfg).await?;
+    let (modules, _) = parse::parse_site(&cfgs).await?;
     println!(
         "{LOOKING_GLASS}{}",
         style(format!(
@@ -72,30 +84,85 @@ async fn run() -> Result<()> {
         .green()
     );
 
+    export::export(&cfgs, &modules).await
+}
+
+async fn run_diff(args: DiffArgs) -> Result<()> {
+    let old_cfgs = Config::get_diff(&args.old, args.base_game_path.clone())?;
+    let new_cfgs = Config::get_diff(&args.new, args.base_game_path)?;
+
     let step = Instant::now();
-    let mut renderer = Renderer::new(&cfg, modules, extra);
-    renderer.render().await?;
+    let (old, _) = parse::parse_site(&old_cfgs).await?;
+    let (new, _) = parse::parse_site(&new_cfgs).await?;
     println!(
-        "{LINK}{}",
+        "{LOOKING_GLASS}{}",
         style(format!(
-            "rendered site in {}",
+            "parsed mod folders in {}",
             HumanDuration(step.elapsed())
         ))
         .green()
     );
 
-    println!(
-        "{SPARKLE}{}",
-        style(format!("Done in {}", HumanDuration(started.elapsed()))).bold()
-    );
+    let report = Report::new(&old, &new);
+    if report.is_empty() {
+        println!("{SPARKLE}{}", style("No balance changes").bold());
+    } else {
+        report.print();
+    }
+    if let Some(output) = &args.output {
+        report.write(output).await?;
+    }
+    Ok(())
+}
+
+async fn run_generate(args: GenerateArgs) -> Result<()> {
+    let cfgs = Config::get(args.clone())?;
+    generate(&cfgs).await?;
+
+    let cfg = &cfgs[0];
     println!(
         "{PACKAGE}Site files available at {}",
         style(cfg.out_dir.display()).bold(),
     );
 
-    if cfg.serve {
-        serve(&cfg).await?;
+    if cfg.watch {
+        watch::watch(args, cfgs).await?;
+    } else if cfg.serve {
+        serve(cfg, None).await?;
     }
 
     Ok(())
 }
+
+async fn generate(cfgs: &[Config]) -> Result<()> {
+    let started = Instant::now();
+
+    let step = Instant::now();
+    let (modules, extra) = parse::parse_site(cfgs).await?;
+    println!(
+        "{LOOKING_GLASS}{}",
+        style(format!(
+            "parsed mod folder in {}",
+            HumanDuration(step.elapsed())
+        ))
+        .green()
+    );
+
+    let step = Instant::now();
+    let mut renderer = Renderer::new(cfgs, modules, extra);
+    renderer.render().await?;
+    println!(
+        "{LINK}{}",
+        style(format!(
+            "rendered site in {}",
+            HumanDuration(step.elapsed())
+        ))
+        .green()
+    );
+
+    println!(
+        "{SPARKLE}{}",
+        style(format!("Done in {}", HumanDuration(started.elapsed()))).bold()
+    );
+    Ok(())
+}
@@ -1,15 +1,47 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashMap,
+    ffi::OsString,
+    fs,
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex},
+};
 
-use crate::{args::Config, parse::manifest::ParserMode::*};
+use tracing::info;
+
+use crate::{
+    args::Config,
+    parse::manifest::{LanguageSpec, ParserMode::*},
+};
 
 #[derive(Clone)]
 pub struct ModFolder {
     cfg: Config,
+    /// The folder text files are read from.
+    text: PathBuf,
+    /// The folder of the first language of the mod, which text files missing
+    /// from `text` are read from.
+    first_text: PathBuf,
 }
 
 impl ModFolder {
     pub fn new(cfg: Config) -> Self {
-        Self { cfg }
+        let text: PathBuf = match cfg.manifest.languages.first() {
+            Some((_, language)) => language.text.clone(),
+            None => "data/text".into(),
+        };
+        Self {
+            cfg,
+            first_text: text.clone(),
+            text,
+        }
+    }
+
+    /// The same folder, with text files in another language.
+    pub fn in_language(&self, language: &LanguageSpec) -> Self {
+        Self {
+            text: language.text.clone(),
+            ..self.clone()
+        }
     }
 
     pub fn banner_png(&self) -> PathBuf {
@@ -35,13 +67,28 @@ impl ModFolder {
         self.root_fallback("data/export_descr_unit.txt")
     }
     pub fn text_expanded_txt(&self) -> PathBuf {
-        self.root_fallback(match self.cfg.manifest.mode {
-            Original | Remastered => "data/text/expanded_bi.txt",
-            Medieval2 => "data/text/expanded.txt",
+        self.text_fallback(match self.cfg.manifest.mode {
+            Original | Remastered => "expanded_bi.txt",
+            Medieval2 => "expanded.txt",
         })
     }
     pub fn text_export_units_txt(&self) -> PathBuf {
-        self.root_fallback("data/text/export_units.txt")
+        self.text_fallback("export_units.txt")
+    }
+    pub fn text_export_buildings_txt(&self) -> PathBuf {
+        self.text_fallback("export_buildings.txt")
+    }
+    /// The names of the campaign's regions and settlements, from the file for
+    /// the campaign if it has one.
+    pub fn text_regions_and_settlement_names_txt(&self) -> PathBuf {
+        let campaign = format!(
+            "{}_regions_and_settlement_names.txt",
+            self.cfg.manifest.campaign
+        );
+        self.existing_path(self.text_fallback(campaign))
+            .unwrap_or_else(|| {
+                self.text_fallback("imperial_campaign_regions_and_settlement_names.txt")
+            })
     }
     pub fn ui_strategy_sd(&self) -> PathBuf {
         self.root_fallback("data/ui/strategy.sd")
@@ -73,21 +120,31 @@ impl ModFolder {
         self.campaign_fallback("map_regions.tga")
     }
     pub fn unit_info_tga(&self, faction: &str, key: &str) -> PathBuf {
+        self.generic_unit_fallback(self.own_unit_info_tga(faction, key))
+    }
+    /// Whether the unit has its own card, rather than falling back to the generic one.
+    pub fn has_unit_info_tga(&self, faction: &str, key: &str) -> bool {
+        self.existing_path(self.own_unit_info_tga(faction, key))
+            .is_some()
+    }
+    fn own_unit_info_tga(&self, faction: &str, key: &str) -> PathBuf {
         let faction = faction.to_lowercase();
         let key = key.to_lowercase();
-        self.generic_unit_fallback(if self.cfg.manifest.unit_info_images {
+        if self.cfg.manifest.unit_info_images {
             let faction = if faction == "mercs" {
                 "merc".into()
             } else {
                 faction.to_lowercase()
             };
-            existing_path(self.root_fallback(format!("data/ui/unit_info/{faction}/{key}_info.tga")))
-                .unwrap_or(self.root_fallback(format!("data/ui/unit_info/merc/{key}_info.tga")))
+            self.existing_path(
+                self.root_fallback(format!("data/ui/unit_info/{faction}/{key}_info.tga")),
+            )
+            .unwrap_or(self.root_fallback(format!("data/ui/unit_info/merc/{key}_info.tga")))
         } else {
             let faction = faction.to_lowercase();
-            existing_path(self.root_fallback(format!("data/ui/units/{faction}/#{key}.tga")))
+            self.existing_path(self.root_fallback(format!("data/ui/units/{faction}/#{key}.tga")))
                 .unwrap_or(self.root_fallback(format!("data/ui/units/mercs/#{key}.tga")))
-        })
+        }
     }
     pub fn faction_symbol_tga(&self, path: impl AsRef<Path>) -> PathBuf {
         self.maybe_missing_data_fallback(path)
@@ -95,26 +152,38 @@ impl ModFolder {
 
     fn root_fallback(&self, path: impl AsRef<Path>) -> PathBuf {
         let path = path.as_ref();
-        existing_path(self.cfg.src_dir.join(path))
-            .unwrap_or_else(|| self.cfg.fallback_dir.join(path))
+        self.layered(|root| vec![root.join(path)])
+    }
+    /// A text file from the folder of the language, or else from that of the
+    /// first language. Medieval II text files can also be compiled to
+    /// `.strings.bin` files next to them.
+    fn text_fallback(&self, path: impl AsRef<Path>) -> PathBuf {
+        let path = path.as_ref();
+        let found = self.root_fallback(self.text.join(path));
+        let compiled = found.with_added_extension("strings.bin");
+        if self.text == self.first_text
+            || self.existing_path(&found).is_some()
+            || self.cfg.manifest.mode == Medieval2 && self.existing_path(compiled).is_some()
+        {
+            return found;
+        }
+        self.root_fallback(self.first_text.join(path))
     }
     fn maybe_missing_data_fallback(&self, path: impl AsRef<Path>) -> PathBuf {
         let path = path.as_ref();
-        existing_path(self.cfg.src_dir.join(path))
-            .or_else(|| existing_path(self.src_data_path().join(path)))
-            .or_else(|| existing_path(self.cfg.fallback_dir.join(path)))
-            .unwrap_or_else(|| self.fallback_data_path().join(path))
+        self.layered(|root| vec![root.join(path), self.data_path(root).join(path)])
     }
     fn campaign_fallback(&self, path: impl AsRef<Path>) -> PathBuf {
         let path = path.as_ref();
-        existing_path(self.src_campaign_path().join(path))
-            .or_else(|| existing_path(self.src_maps_base_path().join(path)))
-            .or_else(|| existing_path(self.fallback_campaign_path().join(path)))
-            .unwrap_or_else(|| self.fallback_maps_base_path().join(path))
+        self.layered(|root| {
+            vec![
+                self.campaign_path(root).join(path),
+                self.maps_base_path(root).join(path),
+            ]
+        })
     }
     fn generic_unit_fallback(&self, path: impl AsRef<Path>) -> PathBuf {
-        existing_path(path.as_ref())
-            .map(Into::into)
+        self.existing_path(path.as_ref())
             .unwrap_or_else(|| self.root_fallback("data/ui/generic/generic_unit_card.tga"))
     }
     fn culture_fallback(&self, culture: impl AsRef<Path>, path: impl AsRef<Path>) -> PathBuf {
@@ -139,26 +208,106 @@ impl ModFolder {
     fn maps_base_path(&self, root: impl AsRef<Path>) -> PathBuf {
         root.as_ref().join("data/world/maps/base")
     }
-    fn src_data_path(&self) -> PathBuf {
-        self.data_path(&self.cfg.src_dir)
-    }
-    fn src_campaign_path(&self) -> PathBuf {
-        self.campaign_path(&self.cfg.src_dir)
-    }
-    fn src_maps_base_path(&self) -> PathBuf {
-        self.maps_base_path(&self.cfg.src_dir)
-    }
-    fn fallback_data_path(&self) -> PathBuf {
-        self.data_path(&self.cfg.fallback_dir)
-    }
-    fn fallback_campaign_path(&self) -> PathBuf {
-        self.campaign_path(&self.cfg.fallback_dir)
-    }
-    fn fallback_maps_base_path(&self) -> PathBuf {
-        self.maps_base_path(&self.cfg.fallback_dir)
+
+    /// Looks for a file in each data root in turn, trying the paths
+    /// `candidates` gives for each root. Loose files come first, then those
+    /// packed in the base game archives. When none exists, the last path of
+    /// the last root is used.
+    fn layered(&self, candidates: impl Fn(&Path) -> Vec<PathBuf>) -> PathBuf {
+        for root in self.cfg.roots.iter() {
+            for path in candidates(root) {
+                if let Some(found) = self.cfg.files.find(&path) {
+                    info!("found {} in {}", found.display(), root.display());
+                    return found;
+                }
+                self.watch_missing(&path);
+            }
+        }
+        let root = &self.cfg.fallback_dir;
+        let paths = candidates(root);
+        for path in paths.iter() {
+            if let Some(entry) = self.cfg.packs.find(root, path) {
+                info!("found {} in {}", path.display(), entry.archive().display());
+                return path.clone();
+            }
+        }
+        paths.last().cloned().expect("no candidate paths")
+    }
+    /// Watches the deepest existing folder on the way to a missing file, as
+    /// that folder changes when the file, or a folder leading to it, is added.
+    fn watch_missing(&self, path: &Path) {
+        if let Some(dir) = path
+            .ancestors()
+            .skip(1)
+            .find_map(|a| self.cfg.files.find(a))
+        {
+            self.cfg.deps.add_dir(dir);
+        }
+    }
+    /// The path of an existing file, whatever the case of its name, or of a
+    /// file in the base game archives.
+    fn existing_path(&self, path: impl AsRef<Path>) -> Option<PathBuf> {
+        let path = path.as_ref();
+        self.cfg.files.find(path).or_else(|| {
+            self.cfg
+                .packs
+                .find(&self.cfg.fallback_dir, path)
+                .map(|_| path.to_path_buf())
+        })
     }
 }
 
-fn existing_path<P: AsRef<Path>>(path: P) -> Option<P> {
-    path.as_ref().exists().then_some(path)
+/// Finds files without regard to the case of their names, as mods made on
+/// Windows seldom agree with themselves or the base game on it. Directory
+/// listings are kept, so each directory is only read once per generation.
+#[derive(Debug, Clone, Default)]
+pub struct FileIndex(Arc<Mutex<HashMap<PathBuf, Listing>>>);
+
+/// The names in a directory, keyed by their lowercase form.
+type Listing = Arc<HashMap<String, OsString>>;
+
+impl FileIndex {
+    /// The path of the existing file or directory that matches `path`, if any.
+    pub fn find(&self, path: &Path) -> Option<PathBuf> {
+        if path.exists() {
+            return Some(path.to_path_buf());
+        }
+        let mut found = PathBuf::new();
+        for component in path.components() {
+            match component {
+                Component::Normal(name) => {
+                    let listing = self.listing(&found);
+                    let name = listing.get(&name.to_string_lossy().to_lowercase())?;
+                    found.push(name);
+                }
+                other => found.push(other),
+            }
+        }
+        Some(found)
+    }
+
+    fn listing(&self, dir: &Path) -> Listing {
+        let mut listings = self.0.lock().expect("poisoned file index");
+        listings
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| {
+                let dir = if dir.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    dir
+                };
+                let entries = fs::read_dir(dir).into_iter().flatten().flatten();
+                Arc::new(
+                    entries
+                        .map(|e| {
+                            (
+                                e.file_name().to_string_lossy().to_lowercase(),
+                                e.file_name(),
+                            )
+                        })
+                        .collect(),
+                )
+            })
+            .clone()
+    }
 }
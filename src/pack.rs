@@ -0,0 +1,332 @@
+//! Files packed in the `.pack` archives of the base game, so that it does not
+//! need to be unpacked to be used for fallbacks.
+//!
+//! An archive starts with two little-endian `u32`s: the number of files, and
+//! the offset of the file table. Each entry of the table has the offset of
+//! the file data, its stored size and its size as `u32`s, then its path as a
+//! `u16` length followed by that many bytes. A file whose stored size is its
+//! size is stored as is. Otherwise, its data starts with a `u32` count of
+//! blocks and the `u32` stored size of each block, followed by the blocks,
+//! each compressed with LZO1X.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::{BufReader, Cursor, Read, Seek, SeekFrom},
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context as _, Result, bail};
+use byteorder::{LittleEndian as LE, ReadBytesExt};
+use tracing::{info, warn};
+
+/// The packed files of each fallback root, read once per generation.
+#[derive(Debug, Clone, Default)]
+pub struct PackIndex(Arc<Mutex<HashMap<PathBuf, Packed>>>);
+
+/// The files in the archives of a root, keyed by their lowercase path.
+type Packed = Arc<HashMap<String, PackEntry>>;
+
+#[derive(Debug, Clone)]
+pub struct PackEntry {
+    archive: PathBuf,
+    offset: u64,
+    stored: u32,
+    size: u32,
+}
+
+impl PackIndex {
+    /// The file at `path` in the archives of `root`, if any.
+    pub fn find(&self, root: &Path, path: &Path) -> Option<PackEntry> {
+        let path = path.strip_prefix(root).ok()?;
+        self.packed(root).get(&packed_name(path)).cloned()
+    }
+
+    fn packed(&self, root: &Path) -> Packed {
+        let mut packed = self.0.lock().expect("poisoned pack index");
+        packed
+            .entry(root.to_path_buf())
+            .or_insert_with(|| Arc::new(read_archives(root)))
+            .clone()
+    }
+}
+
+impl PackEntry {
+    /// The archive this file is packed in.
+    pub fn archive(&self) -> &Path {
+        &self.archive
+    }
+
+    pub async fn read(&self) -> Result<Vec<u8>> {
+        let entry = self.clone();
+        tokio::task::spawn_blocking(move || entry.read_blocking()).await?
+    }
+
+    fn read_blocking(&self) -> Result<Vec<u8>> {
+        let mut file = File::open(&self.archive)?;
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut buf = vec![0; self.stored as usize];
+        file.read_exact(&mut buf)?;
+        if self.stored == self.size {
+            return Ok(buf);
+        }
+
+        let mut r = Cursor::new(&buf);
+        let count = r.read_u32::<LE>()?;
+        let sizes = (0..count)
+            .map(|_| r.read_u32::<LE>())
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut data = Vec::with_capacity(self.size as usize);
+        for size in sizes {
+            let mut block = vec![0; size as usize];
+            r.read_exact(&mut block)?;
+            lzo1x_decompress(&block, &mut data)?;
+        }
+        if data.len() != self.size as usize {
+            bail!("expected {} bytes, got {}", self.size, data.len());
+        }
+        Ok(data)
+    }
+}
+
+/// The files in the archives of the `packs` folder of `root`. Archives that
+/// come later in name order replace the files of earlier ones.
+fn read_archives(root: &Path) -> HashMap<String, PackEntry> {
+    let Ok(dir) = fs::read_dir(root.join("packs")) else {
+        return HashMap::new();
+    };
+    let mut archives: Vec<_> = dir
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case("pack"))
+        })
+        .collect();
+    archives.sort();
+
+    let mut packed = HashMap::new();
+    for archive in archives {
+        match read_table(&archive) {
+            Ok(entries) => {
+                info!("found {} files in {}", entries.len(), archive.display());
+                packed.extend(entries);
+            }
+            Err(e) => warn!("skipping {}: {e:#}", archive.display()),
+        }
+    }
+    packed
+}
+
+fn read_table(archive: &Path) -> Result<Vec<(String, PackEntry)>> {
+    let mut r = BufReader::new(
+        File::open(archive).with_context(|| format!("opening {}", archive.display()))?,
+    );
+    let count = r.read_u32::<LE>()?;
+    let table = r.read_u32::<LE>()?;
+    r.seek(SeekFrom::Start(table as u64))?;
+    (0..count)
+        .map(|_| -> Result<_> {
+            let offset = r.read_u32::<LE>()? as u64;
+            let stored = r.read_u32::<LE>()?;
+            let size = r.read_u32::<LE>()?;
+            let len = r.read_u16::<LE>()?;
+            let mut name = vec![0; len as usize];
+            r.read_exact(&mut name)?;
+            let name = String::from_utf8_lossy(&name).replace('\\', "/");
+            let entry = PackEntry {
+                archive: archive.to_path_buf(),
+                offset,
+                stored,
+                size,
+            };
+            Ok((packed_name(Path::new(&name)), entry))
+        })
+        .collect::<Result<_>>()
+        .context("invalid pack file")
+}
+
+/// The key of a path relative to the game folder, as the archives do not
+/// agree with mods on the case of names.
+fn packed_name(path: &Path) -> String {
+    path.components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => Some(name.to_string_lossy().to_lowercase()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Decompresses an LZO1X block, appending it to `out`. Matches may refer to
+/// the data of earlier blocks.
+fn lzo1x_decompress(input: &[u8], out: &mut Vec<u8>) -> Result<()> {
+    let mut ip = 0;
+    let byte = |ip: &mut usize| -> Result<usize> {
+        let b = *input.get(*ip).context("truncated LZO block")?;
+        *ip += 1;
+        Ok(b as usize)
+    };
+    // Lengths that do not fit in their instruction go on in zero bytes
+    let length = |ip: &mut usize, base: usize| -> Result<usize> {
+        let mut len = 0;
+        loop {
+            match byte(ip)? {
+                0 => len += 255,
+                b => return Ok(len + base + b),
+            }
+        }
+    };
+    let literals = |ip: &mut usize, out: &mut Vec<u8>, n: usize| -> Result<()> {
+        let bytes = input.get(*ip..*ip + n).context("truncated LZO block")?;
+        out.extend_from_slice(bytes);
+        *ip += n;
+        Ok(())
+    };
+    let copy = |out: &mut Vec<u8>, distance: usize, n: usize| -> Result<()> {
+        if distance == 0 || distance > out.len() {
+            bail!("invalid LZO match distance");
+        }
+        let from = out.len() - distance;
+        for i in 0..n {
+            out.push(out[from + i]);
+        }
+        Ok(())
+    };
+
+    // How many literals came right before, which changes what short matches
+    // mean: none, a few, or a run of four or more
+    let mut state = 0;
+    if input.first().is_some_and(|b| *b > 17) {
+        let n = byte(&mut ip)? - 17;
+        literals(&mut ip, out, n)?;
+        state = if n < 4 { n } else { 4 };
+    }
+    loop {
+        let t = byte(&mut ip)?;
+        let (distance, len, next) = if t >= 64 {
+            let distance = 1 + ((t >> 2) & 7) + (byte(&mut ip)? << 3);
+            (distance, (t >> 5) + 1, t & 3)
+        } else if t >= 32 {
+            let len = match t & 31 {
+                0 => length(&mut ip, 31)?,
+                n => n,
+            } + 2;
+            let d = byte(&mut ip)? | (byte(&mut ip)? << 8);
+            (1 + (d >> 2), len, d & 3)
+        } else if t >= 16 {
+            let len = match t & 7 {
+                0 => length(&mut ip, 7)?,
+                n => n,
+            } + 2;
+            let d = byte(&mut ip)? | (byte(&mut ip)? << 8);
+            let distance = ((t & 8) << 11) + (d >> 2);
+            if distance == 0 {
+                break;
+            }
+            (distance + 0x4000, len, d & 3)
+        } else if state == 0 {
+            let n = match t {
+                0 => length(&mut ip, 15)?,
+                n => n,
+            } + 3;
+            literals(&mut ip, out, n)?;
+            state = 4;
+            continue;
+        } else if state < 4 {
+            (1 + (t >> 2) + (byte(&mut ip)? << 2), 2, t & 3)
+        } else {
+            (0x801 + (t >> 2) + (byte(&mut ip)? << 2), 3, t & 3)
+        };
+        copy(out, distance, len)?;
+        literals(&mut ip, out, next)?;
+        state = next;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use byteorder::WriteBytesExt;
+
+    use super::*;
+
+    /// Writes an archive of files given as their path, their stored data and
+    /// their size.
+    fn write_archive(name: &str, files: &[(&str, &[u8], u32)]) -> PathBuf {
+        let data: Vec<u8> = files
+            .iter()
+            .flat_map(|(_, data, _)| data.to_vec())
+            .collect();
+        let mut buf = vec![];
+        buf.write_u32::<LE>(files.len() as u32).unwrap();
+        buf.write_u32::<LE>(8 + data.len() as u32).unwrap();
+        buf.extend_from_slice(&data);
+        let mut offset = 8;
+        for (path, data, size) in files {
+            buf.write_u32::<LE>(offset).unwrap();
+            buf.write_u32::<LE>(data.len() as u32).unwrap();
+            buf.write_u32::<LE>(*size).unwrap();
+            buf.write_u16::<LE>(path.len() as u16).unwrap();
+            buf.extend_from_slice(path.as_bytes());
+            offset += data.len() as u32;
+        }
+        let archive =
+            std::env::temp_dir().join(format!("faust-{}-{name}.pack", std::process::id()));
+        fs::write(&archive, buf).unwrap();
+        archive
+    }
+
+    fn read(archive: &Path, path: &str) -> Result<Vec<u8>> {
+        let entries: HashMap<_, _> = read_table(archive)?.into_iter().collect();
+        let res = entries[path].read_blocking();
+        fs::remove_file(archive)?;
+        res
+    }
+
+    #[test]
+    fn stored_entry() {
+        let archive = write_archive("stored", &[("data\\text\\Names.txt", b"hello", 5)]);
+        assert_eq!(read(&archive, "data/text/names.txt").unwrap(), b"hello");
+    }
+
+    #[test]
+    fn compressed_entry_with_match_across_blocks() {
+        // Four literals, then two more and a match of the first four, six
+        // bytes back in the previous block
+        let first: &[u8] = &[17 + 4, b'a', b'b', b'c', b'd', 0x11, 0, 0];
+        let second: &[u8] = &[17 + 2, b'x', b'y', 0x74, 0, 0x11, 0, 0];
+        let mut data = vec![];
+        for n in [2, first.len(), second.len()] {
+            data.write_u32::<LE>(n as u32).unwrap();
+        }
+        data.extend_from_slice(first);
+        data.extend_from_slice(second);
+        let archive = write_archive("compressed", &[("data/a.txt", &data, 10)]);
+        assert_eq!(read(&archive, "data/a.txt").unwrap(), b"abcdxyabcd");
+    }
+
+    #[test]
+    fn truncated_table() {
+        let archive = write_archive("truncated", &[]);
+        let mut buf = fs::read(&archive).unwrap();
+        buf[0] = 1;
+        fs::write(&archive, buf).unwrap();
+        assert!(read_table(&archive).is_err());
+        fs::remove_file(archive).unwrap();
+    }
+
+    #[test]
+    fn truncated_block() {
+        let err = lzo1x_decompress(&[17 + 4, b'a'], &mut vec![]).unwrap_err();
+        assert_eq!(err.to_string(), "truncated LZO block");
+    }
+
+    #[test]
+    fn invalid_distance() {
+        // A match four bytes back after a single literal
+        let err = lzo1x_decompress(&[17 + 1, b'a', 0x6c, 0, 0x11, 0, 0], &mut vec![]).unwrap_err();
+        assert_eq!(err.to_string(), "invalid LZO match distance");
+    }
+}
@@ -2,46 +2,55 @@ use std::{collections::HashMap, str::pattern::Pattern};
 
 use anyhow::{Context as _, Result, anyhow};
 
-use crate::{parse::manifest::ParserMode, utils::parse_maybe_float_int};
+use crate::{
+    parse::{diagnostic::at_line, manifest::ParserMode},
+    utils::parse_maybe_float_int,
+};
 
 pub fn parse(data: impl AsRef<str>, _: ParserMode) -> Result<Vec<Pool>> {
     data.as_ref()
         .lines() // split lines
-        .filter_map(|l| l.split(';').nth(0)) // strip comments
-        .map(|l| l.trim()) // strip leading/trailing whitespace
-        .filter(|l| l.len() > 0) // strip empty lines
-        .fold(vec![], |mut acc: Vec<Vec<String>>, line| {
+        .enumerate() // number lines
+        .filter_map(|(i, l)| l.split(';').nth(0).map(|l| (i + 1, l))) // strip comments
+        .map(|(i, l)| (i, l.trim())) // strip leading/trailing whitespace
+        .filter(|(_, l)| l.len() > 0) // strip empty lines
+        .fold(vec![], |mut acc: Vec<Vec<(usize, String)>>, (i, line)| {
             if line.starts_with("pool") {
-                acc.push(vec![line.into()]);
+                acc.push(vec![(i, line.into())]);
             } else {
                 let idx = acc.len() - 1;
-                acc[idx].push(line.into());
+                acc[idx].push((i, line.into()));
             }
             acc
         })
         .into_iter()
-        .map(|s| parse_pool(&s).with_context(|| format!("parsing pool: {s:?}")))
+        .map(|s| parse_pool(&s).map_err(|e| at_line(e, s[0].0, None)))
         .collect()
 }
 
-fn parse_pool(lines: &[String]) -> Result<Pool> {
+fn parse_pool(lines: &[(usize, String)]) -> Result<Pool> {
     let raw: Vec<_> = lines
         .iter()
-        .map(|line| {
+        .map(|(i, line)| {
             let mut split = line.split(char::is_whitespace);
             let keyword = split
                 .next()
                 .ok_or_else(|| anyhow!("line didn't start with keyword"))
                 .with_context(|| format!("parsing line {line}"))?;
             let value = split.remainder().map(|s| s.trim());
-            Ok((keyword, value))
+            Ok((*i, keyword, value))
         })
         .collect::<Result<_>>()?;
-    let entries: HashMap<_, _> = raw.iter().copied().collect();
+    let entries: HashMap<_, _> = raw.iter().map(|&(_, k, v)| (k, v)).collect();
+    let regions_line = raw
+        .iter()
+        .find_map(|&(i, k, _)| (k == "regions").then_some(i))
+        .unwrap_or(lines[0].0);
 
     Ok(Pool {
         id: require_line_value(&entries, "pool")?.into(),
-        regions: require_line_value(&entries, "regions")?
+        regions: require_line_value(&entries, "regions")
+            .map_err(|e| at_line(e, regions_line, None))?
             .split(OPT_COMMA)
             .map(|s| s.trim())
             .filter(|s| s.len() > 0)
@@ -49,16 +58,19 @@ fn parse_pool(lines: &[String]) -> Result<Pool> {
             .collect(),
         units: raw
             .into_iter()
-            .filter(|(s, _)| *s == "unit")
-            .map(|(_, s)| {
-                parse_unit(s.ok_or_else(|| anyhow!("missing pool entry data"))?)
+            .filter(|(_, s, _)| *s == "unit")
+            .map(|(i, _, s)| {
+                s.ok_or_else(|| anyhow!("missing pool entry data"))
+                    .and_then(|s| parse_unit(s, i))
                     .with_context(|| format!("parsing pool entry: {s:?}"))
+                    .map_err(|e| at_line(e, i, s))
             })
             .collect::<Result<_>>()?,
+        line: lines[0].0,
     })
 }
 
-fn parse_unit(line: &str) -> Result<Unit> {
+fn parse_unit(line: &str, line_no: usize) -> Result<Unit> {
     let mut split = line.split(TAB_OR_COMMA);
     let id = split
         .next()
@@ -127,6 +139,7 @@ fn parse_unit(line: &str) -> Result<Unit> {
         } else {
             vec![]
         },
+        line: line_no,
     })
 }
 
@@ -162,14 +175,15 @@ fn require_line_value<'a>(entries: &'a PoolEntries, key: &str) -> Result<&'a str
 const OPT_COMMA: &[char] = &[',', ' '];
 const TAB_OR_COMMA: &[char] = &[',', '\t'];
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Pool {
     pub id: String,
     pub regions: Vec<String>,
     pub units: Vec<Unit>,
+    pub line: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Unit {
     pub id: String,
     pub exp: u32,
@@ -178,4 +192,5 @@ pub struct Unit {
     pub max: u32,
     pub initial: u32,
     pub restrict: Vec<String>,
+    pub line: usize,
 }
@@ -74,7 +74,7 @@ fn require_line_value<'a>(entries: &'a ModelEntries, key: &str) -> Result<&'a st
     get_line_value(entries, key).ok_or_else(|| anyhow!("{key} not found"))
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Model {
     pub id: String,
     pub skeleton: String,
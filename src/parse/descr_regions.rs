@@ -1,72 +1,111 @@
+use std::collections::BTreeMap;
+
 use anyhow::{Context as _, Result, anyhow};
 
-use crate::parse::manifest::ParserMode;
+use crate::parse::{diagnostic::at_line, manifest::ParserMode};
 
 pub fn parse(data: impl AsRef<str>, _: ParserMode) -> Result<Vec<Region>> {
     data.as_ref()
         .lines() // split lines
-        .filter_map(|l| l.split(';').nth(0)) // strip comments
-        .filter(|l| l.len() > 0) // strip empty lines
-        .fold(vec![], |mut acc: Vec<Vec<String>>, line| {
+        .enumerate() // number lines
+        .filter_map(|(i, l)| l.split(';').nth(0).map(|l| (i + 1, l))) // strip comments
+        .filter(|(_, l)| l.len() > 0) // strip empty lines
+        .fold(vec![], |mut acc: Vec<Vec<(usize, String)>>, (i, line)| {
             if line.trim().len() > 0 {
                 if !line.starts_with([' ', '\t']) {
-                    acc.push(vec![line.trim().into()]);
+                    acc.push(vec![(i, line.trim().into())]);
                 } else {
                     let idx = acc.len() - 1;
-                    acc[idx].push(line.trim().into());
+                    acc[idx].push((i, line.trim().into()));
                 }
             }
             acc
         })
         .into_iter()
-        .map(|s| parse_region(&s).with_context(|| format!("parsing region: {s:?}")))
+        .map(|s| parse_region(&s).map_err(|e| at_line(e, s[0].0, None)))
         .collect()
 }
 
-fn parse_region(lines: &[String]) -> Result<Region> {
-    let legion = lines.iter().find(|l| l.starts_with("legion:"));
+fn parse_region(lines: &[(usize, String)]) -> Result<Region> {
+    let legion = lines.iter().find(|(_, l)| l.starts_with("legion:"));
     let legion = legion
-        .and_then(|l| l.split(':').remainder().map(str::trim))
+        .and_then(|(_, l)| l.split(':').remainder().map(str::trim))
         .map(Into::into);
     let lines: Vec<_> = lines
         .into_iter()
-        .filter(|l| !l.starts_with("legion:"))
+        .filter(|(_, l)| !l.starts_with("legion:"))
         .collect();
+    let last_line = lines.last().map_or(0, |(i, _)| *i);
+    let religions = match lines.iter().find(|(_, l)| l.starts_with("religions")) {
+        Some((i, l)) => parse_religions(l).map_err(|e| at_line(e, *i, None))?,
+        None => BTreeMap::new(),
+    };
 
-    let mut color_it = lines
+    let (color_line, color) = lines
         .get(4)
-        .ok_or_else(|| anyhow!("missing color line"))?
-        .split_whitespace()
-        .map(|s| s.parse().context("parsing color"));
+        .ok_or_else(|| anyhow!("missing color line"))
+        .map_err(|e| at_line(e, last_line, None))?;
+    let mut color_it = color.split_whitespace().map(|s| {
+        s.parse()
+            .context("parsing color")
+            .map_err(|e| at_line(e, *color_line, Some(s)))
+    });
+    let mut next_color = |name| {
+        color_it
+            .next()
+            .ok_or_else(|| anyhow!("missing {name}"))
+            .flatten()
+            .map_err(|e| at_line(e, *color_line, None))
+    };
     Ok(Region {
         id: lines
             .get(0)
-            .copied()
             .ok_or_else(|| anyhow!("missing id line"))?
+            .1
             .clone(),
         legion,
         city: lines
             .get(1)
-            .copied()
             .ok_or_else(|| anyhow!("missing city line"))?
+            .1
             .clone(),
         color: (
-            color_it.next().ok_or_else(|| anyhow!("missing red"))??,
-            color_it.next().ok_or_else(|| anyhow!("missing green"))??,
-            color_it.next().ok_or_else(|| anyhow!("missing blue"))??,
+            next_color("red")?,
+            next_color("green")?,
+            next_color("blue")?,
         ),
         hidden_resources: lines
             .get(5)
-            .ok_or_else(|| anyhow!("missing hidden_resources line"))?
+            .ok_or_else(|| anyhow!("missing hidden_resources line"))
+            .map_err(|e| at_line(e, last_line, None))?
+            .1
             .split(OPT_COMMA)
             .map(str::trim)
             .filter(|r| *r != "none")
             .map(Into::into)
             .collect(),
-        // religions: todo!(),
+        religions,
     })
 }
 
+/// Parses a line like `religions { catholic 70 orthodox 0 pagan 30 }`.
+fn parse_religions(line: &str) -> Result<BTreeMap<String, u32>> {
+    let mut words = line
+        .trim_start_matches("religions")
+        .split(['{', '}'])
+        .flat_map(str::split_whitespace);
+    let mut religions = BTreeMap::new();
+    while let Some(religion) = words.next() {
+        let amount = words
+            .next()
+            .ok_or_else(|| anyhow!("missing amount of {religion}"))?
+            .parse()
+            .with_context(|| format!("parsing amount of {religion}"))?;
+        religions.insert(religion.into(), amount);
+    }
+    Ok(religions)
+}
+
 const OPT_COMMA: &[char] = &[',', ' '];
 const COMMA: &str = ",";
 
@@ -77,5 +116,14 @@ pub struct Region {
     pub city: String,
     pub color: (u8, u8, u8),
     pub hidden_resources: Vec<String>,
-    // pub religions: HashMap<String, u32>,
+    /// The percentage of each religion (Medieval II only).
+    pub religions: BTreeMap<String, u32>,
+}
+
+/// The religion with the largest share, if there are any religions.
+pub fn majority_religion(religions: &BTreeMap<String, u32>) -> Option<&str> {
+    religions
+        .iter()
+        .max_by_key(|(_, amount)| **amount)
+        .map(|(religion, _)| religion.as_str())
 }
@@ -14,11 +14,13 @@ pub fn parse(data: impl AsRef<str>, mode: ParserMode) -> Result<Vec<Faction>> {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Faction {
     pub id: String,
     pub name: String,
     pub culture: String,
+    pub religion: Option<String>,
     pub logo_path: PathBuf,
     pub logo_index: String,
+    pub line: usize,
 }
@@ -7,27 +7,29 @@ use super::Faction;
 pub fn parse(data: impl AsRef<str>) -> Result<Vec<Faction>> {
     data.as_ref()
         .lines() // split lines
-        .filter_map(|l| l.split(';').nth(0)) // strip comments
-        .map(|l| l.trim()) // strip leading/trailing whitespace
-        .filter(|l| l.len() > 0) // strip empty lines
-        .fold(vec![], |mut acc: Vec<Vec<String>>, line| {
+        .enumerate() // number lines
+        .filter_map(|(i, l)| l.split(';').nth(0).map(|l| (i + 1, l))) // strip comments
+        .map(|(i, l)| (i, l.trim())) // strip leading/trailing whitespace
+        .filter(|(_, l)| l.len() > 0) // strip empty lines
+        .fold(vec![], |mut acc: Vec<(usize, Vec<String>)>, (i, line)| {
             if line.starts_with("faction") {
-                acc.push(vec![line.into()]);
+                acc.push((i, vec![line.into()]));
             } else {
                 let idx = acc.len() - 1;
-                acc[idx].push(line.into());
+                acc[idx].1.push(line.into());
             }
             acc
         })
         .into_iter()
-        .map(|s| parse_faction(&s).with_context(|| format!("parsing line: {s:?}")))
+        .map(|(i, s)| parse_faction(&s, i).with_context(|| format!("parsing line: {s:?}")))
         .collect()
 }
 
-fn parse_faction(lines: &[String]) -> Result<Faction> {
+fn parse_faction(lines: &[String], line_no: usize) -> Result<Faction> {
     let mut id = String::new();
     let mut name = String::new();
     let mut culture = String::new();
+    let mut religion = None;
     let mut logo_path = PathBuf::new();
     let mut logo_index = String::new();
     for line in lines.iter() {
@@ -53,6 +55,8 @@ fn parse_faction(lines: &[String]) -> Result<Faction> {
                 .ok_or_else(|| anyhow!("line didn't have a value"))
                 .with_context(|| format!("parsing line {line}"))?
                 .into();
+        } else if keyword == "religion" {
+            religion = value.map(Into::into);
         } else if keyword == "loading_logo" {
             logo_path = fix_path_with_parent(
                 value
@@ -71,8 +75,10 @@ fn parse_faction(lines: &[String]) -> Result<Faction> {
         id,
         name,
         culture,
+        religion,
         logo_path,
         logo_index,
+        line: line_no,
     })
 }
 
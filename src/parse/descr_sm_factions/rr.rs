@@ -41,6 +41,7 @@ pub fn parse(data: impl AsRef<str>) -> Result<Vec<Faction>> {
                     .and_then(|s| s.as_str())
                     .map(Into::into)
                     .ok_or_else(|| anyhow!("no `culture` found for {id}"))?,
+                religion: m.get("religion").and_then(|s| s.as_str()).map(Into::into),
                 logo_path: m
                     .get("logos")
                     .and_then(|s| s.as_object())
@@ -55,6 +56,12 @@ pub fn parse(data: impl AsRef<str>) -> Result<Vec<Faction>> {
                     .and_then(|s| s.as_str())
                     .map(Into::into)
                     .unwrap_or_else(String::new),
+                // the JSON5 parser doesn't keep positions, so find the faction's key instead
+                line: data
+                    .as_ref()
+                    .lines()
+                    .position(|l| l.contains(&format!("\"{id}\"")))
+                    .map_or(0, |i| i + 1),
                 id,
             })
         })
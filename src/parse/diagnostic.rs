@@ -0,0 +1,105 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+/// A problem in the mod data that doesn't stop the catalog from being built.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub path: PathBuf,
+    pub line: usize,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(path: impl AsRef<Path>, line: usize, message: impl Into<String>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.path.display(), self.line, self.message)
+    }
+}
+
+/// A parse error at a line of a data file.
+///
+/// The parsers only know the line and, when they can tell, the offending token;
+/// [`in_file`] adds the path, the column, and the text of the line.
+#[derive(Debug, Clone, Error)]
+pub struct SourceError {
+    pub path: Option<PathBuf>,
+    pub line: usize,
+    pub column: usize,
+    pub token: Option<String>,
+    pub text: String,
+    pub message: String,
+}
+
+impl Display for SourceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.path {
+            Some(path) => write!(f, "{}:", path.display())?,
+            None => write!(f, "line ")?,
+        }
+        writeln!(f, "{}:{}: {}", self.line, self.column, self.message)?;
+        let gutter = self.line.to_string().len();
+        writeln!(f, "{:gutter$} |", "")?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        // keep tabs so the caret lines up with the text above it
+        let indent: String = self
+            .text
+            .chars()
+            .take(self.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        write!(f, "{:gutter$} | {indent}^", "")
+    }
+}
+
+/// Locates an error at a line, unless it was already located more precisely.
+pub fn at_line(err: anyhow::Error, line: usize, token: Option<&str>) -> anyhow::Error {
+    if err.is::<SourceError>() {
+        return err;
+    }
+    SourceError {
+        path: None,
+        line,
+        column: 1,
+        token: token.map(Into::into),
+        text: String::new(),
+        message: format!("{err:#}"),
+    }
+    .into()
+}
+
+/// Completes a located error with the file it came from.
+pub fn in_file(err: anyhow::Error, path: &Path, data: &str) -> anyhow::Error {
+    let Some(located) = err.downcast_ref::<SourceError>() else {
+        return err.context(format!("parsing {}", path.display()));
+    };
+    let text = data
+        .lines()
+        .nth(located.line.saturating_sub(1))
+        .unwrap_or("");
+    let offset = located
+        .token
+        .as_deref()
+        .filter(|t| !t.is_empty())
+        .and_then(|t| text.find(t))
+        .unwrap_or_else(|| text.len() - text.trim_start().len());
+    SourceError {
+        path: Some(path.to_path_buf()),
+        column: text[..offset].chars().count() + 1,
+        text: text.trim_end().into(),
+        ..located.clone()
+    }
+    .into()
+}
@@ -1,9 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use serde::{Deserialize, Serialize};
 
 use crate::parse::{
-    descr_regions::Region, descr_sm_factions::Faction, export_descr_buildings::Requires,
+    descr_regions::{Region, majority_religion},
+    descr_sm_factions::Faction,
+    export_descr_buildings::{Building, Requires},
 };
 
 #[derive(Default, Clone, Debug, Serialize, Deserialize)]
@@ -19,11 +21,29 @@ pub struct Evaluator {
     hidden_resource: Option<EvaluatorChoices>,
     #[serde(skip_serializing_if = "Option::is_none")]
     major_event: Option<EvaluatorChoices>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    event_counter: Option<EvaluatorCounters>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_toggled: Option<EvaluatorChoices>,
+    /// Building chains or levels, by name; a level takes precedence over its
+    /// chain for `building_present_min_level`, and a level that is built means
+    /// the lower levels of its chain are too.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    building_present: Option<EvaluatorChoices>,
+    /// The chain and position of each building level, by name.
+    #[serde(skip)]
+    levels: HashMap<String, (String, usize)>,
+    /// The religions of the region, by percentage.
+    #[serde(skip)]
+    religions: Option<BTreeMap<String, u32>>,
+    #[serde(skip)]
+    official_religion: Option<String>,
 }
 
 impl Evaluator {
     pub fn faction(faction: &Faction) -> Self {
         Self {
+            official_religion: faction.religion.clone(),
             faction: Some(EvaluatorChoices {
                 map: [
                     (faction.id.clone(), true),
@@ -37,6 +57,18 @@ impl Evaluator {
         }
     }
 
+    /// The same evaluator, with the building levels of the mod known, so that
+    /// levels can be compared with the other levels of their chain.
+    pub fn with_levels(&self, buildings: &[Building]) -> Self {
+        Self {
+            levels: buildings
+                .iter()
+                .map(|b| (b.name.clone(), (b.chain.clone(), b.index)))
+                .collect(),
+            ..self.clone()
+        }
+    }
+
     pub fn region(region: &Region, faction: Option<&Faction>) -> Self {
         Self {
             hidden_resource: Some(EvaluatorChoices {
@@ -47,6 +79,7 @@ impl Evaluator {
                     .collect(),
                 default: Some(false),
             }),
+            religions: (!region.religions.is_empty()).then(|| region.religions.clone()),
             ..if let Some(faction) = faction {
                 Self::faction(faction)
             } else {
@@ -70,6 +103,22 @@ impl EvaluatorChoices {
     }
 }
 
+/// The values of event counters; `event_counter` conditions hold when the
+/// counter is at least the given amount.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvaluatorCounters {
+    #[serde(flatten)]
+    map: HashMap<String, u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default: Option<u32>,
+}
+
+impl EvaluatorCounters {
+    fn get(&self, counter: &str) -> Option<u32> {
+        self.map.get(counter).copied().or(self.default)
+    }
+}
+
 pub fn evaluate(req: &Requires, aliases: &HashMap<String, Requires>, eval: &Evaluator) -> bool {
     do_evaluate(req, aliases, eval).unwrap_or(true)
 }
@@ -90,6 +139,33 @@ fn do_evaluate(
             factionwide: false,
         } => eval.hidden_resource.as_ref().and_then(|r| r.get(id)),
         Requires::MajorEvent(event) => eval.major_event.as_ref().and_then(|r| r.get(event)),
+        Requires::EventCount { event, count } => eval
+            .event_counter
+            .as_ref()
+            .and_then(|r| r.get(event))
+            .map(|value| value >= *count),
+        Requires::Religion { id, cmp, amount } => eval
+            .religions
+            .as_ref()
+            .map(|r| cmp.compare(r.get(id).copied().unwrap_or(0), *amount)),
+        Requires::MajorityReligion(id) => eval
+            .religions
+            .as_ref()
+            .map(|r| majority_religion(r) == Some(id.as_str())),
+        Requires::OfficialReligion => eval
+            .religions
+            .as_ref()
+            .zip(eval.official_religion.as_ref())
+            .map(|(r, official)| majority_religion(r) == Some(official.as_str())),
+        Requires::IsToggled(toggle) => eval.is_toggled.as_ref().and_then(|r| r.get(toggle)),
+        Requires::BuildingPresent { id, level, .. } => {
+            eval.building_present.as_ref().and_then(|r| {
+                level
+                    .as_ref()
+                    .and_then(|level| level_present(eval, r, level))
+                    .or_else(|| r.get(id))
+            })
+        }
         Requires::Factions(factions) => {
             let res = factions
                 .iter()
@@ -136,3 +212,80 @@ fn do_evaluate(
         _ => eval.default,
     }
 }
+
+/// Whether a building chain is built up to at least `level`. Besides `level`
+/// itself, a higher level of the chain that is built means it is, and a lower
+/// level that is not built means it is not.
+fn level_present(eval: &Evaluator, choices: &EvaluatorChoices, level: &str) -> Option<bool> {
+    if let Some(present) = choices.map.get(level) {
+        return Some(*present);
+    }
+    let (chain, index) = eval.levels.get(level)?;
+    let marked = |present: bool| {
+        choices.map.iter().any(|(name, p)| {
+            *p == present
+                && eval.levels.get(name).is_some_and(|(c, i)| {
+                    c == chain && if present { i >= index } else { i <= index }
+                })
+        })
+    };
+    if marked(true) {
+        Some(true)
+    } else if marked(false) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Resolves the parts of a requirement that the evaluator decides, expanding
+/// aliases, and returns the conditions left. `Requires::None` is returned when
+/// the requirement is always met, and `Requires::False` when it never is.
+pub fn simplify(req: &Requires, aliases: &HashMap<String, Requires>, eval: &Evaluator) -> Requires {
+    match req {
+        Requires::None => Requires::None,
+        Requires::Alias(id) => simplify(aliases.get(id).unwrap_or(&Requires::None), aliases, eval),
+        Requires::Not(requires) => match simplify(requires, aliases, eval) {
+            Requires::None => Requires::False,
+            Requires::False => Requires::None,
+            requires => Requires::Not(Box::new(requires)),
+        },
+        Requires::And(items) => {
+            let mut left = vec![];
+            for item in items {
+                match simplify(item, aliases, eval) {
+                    Requires::None => {}
+                    Requires::False => return Requires::False,
+                    Requires::And(items) => left.extend(items),
+                    item => left.push(item),
+                }
+            }
+            match left.len() {
+                0 => Requires::None,
+                1 => left.remove(0),
+                _ => Requires::And(left),
+            }
+        }
+        Requires::Or(items) => {
+            let mut left = vec![];
+            for item in items {
+                match simplify(item, aliases, eval) {
+                    Requires::None => return Requires::None,
+                    Requires::False => {}
+                    Requires::Or(items) => left.extend(items),
+                    item => left.push(item),
+                }
+            }
+            match left.len() {
+                0 => Requires::False,
+                1 => left.remove(0),
+                _ => Requires::Or(left),
+            }
+        }
+        _ => match do_evaluate(req, aliases, eval) {
+            Some(true) => Requires::None,
+            Some(false) => Requires::False,
+            None => req.clone(),
+        },
+    }
+}
@@ -5,11 +5,14 @@ use std::{
 };
 
 use anyhow::{Context as _, Result, anyhow, bail};
-use pest::{Parser as _, iterators::Pairs};
+use pest::{Parser as _, error::InputLocation, iterators::Pairs};
 use thiserror::Error;
 
 use crate::{
-    parse::manifest::ParserMode::{self, *},
+    parse::{
+        diagnostic::at_line,
+        manifest::ParserMode::{self, *},
+    },
     utils::parse_maybe_float_int,
 };
 
@@ -28,9 +31,10 @@ pub fn parse(
     let mut lines = data
         .as_ref()
         .lines() // split lines
-        .filter_map(|l| l.split(';').nth(0)) // strip comments
-        .map(|l| l.trim()) // strip leading/trailing whitespace
-        .filter(|l| l.len() > 0); // strip empty lines
+        .enumerate() // number lines
+        .filter_map(|(i, l)| l.split(';').nth(0).map(|l| (i + 1, l))) // strip comments
+        .map(|(i, l)| (i, l.trim())) // strip leading/trailing whitespace
+        .filter(|(_, l)| l.len() > 0); // strip empty lines
 
     let mut aliases = HashMap::new();
     if mode == Original || mode == Remastered {
@@ -40,18 +44,24 @@ pub fn parse(
         );
     }
     let mut buildings = Vec::new();
+    let mut chain = "";
     loop {
         match lines.next() {
-            Some(line) if line.starts_with("alias") => {
+            Some((_, line)) if line.split_whitespace().next() == Some("building") => {
+                chain = line.split_whitespace().nth(1).unwrap_or_default();
+            }
+            Some((i, line)) if line.starts_with("alias") => {
                 let (k, v) = parse_alias(&mut lines, line)
-                    .with_context(|| format!("parsing alias {line}"))?;
+                    .with_context(|| format!("parsing alias {line}"))
+                    .map_err(|e| at_line(e, i, None))?;
                 aliases.insert(k, v);
             }
-            Some(line) if line.starts_with("levels") => {
-                parse_building(&mut lines, line, &mut buildings, mode)
-                    .with_context(|| format!("parsing building levels {line:?}"))?
+            Some((i, line)) if line.starts_with("levels") => {
+                parse_building(&mut lines, chain, line, &mut buildings, mode)
+                    .with_context(|| format!("parsing building levels {line:?}"))
+                    .map_err(|e| at_line(e, i, None))?
             }
-            Some(line) if line.starts_with("tags") => skip_block(&mut lines),
+            Some((_, line)) if line.starts_with("tags") => skip_block(&mut lines),
             Some(_) => {}
             None => break,
         }
@@ -61,7 +71,7 @@ pub fn parse(
 }
 
 fn parse_alias<'a>(
-    lines: &mut impl Iterator<Item = &'a str>,
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
     header: &'a str,
 ) -> Result<(String, Requires)> {
     let id = header
@@ -70,16 +80,17 @@ fn parse_alias<'a>(
         .ok_or_else(|| anyhow!("missing alias name"))?;
 
     let block = get_block(lines)?;
-    let requires = block
+    let (line, requires) = block
         .into_iter()
-        .find(|l| l.starts_with("requires"))
+        .find(|(_, l)| l.starts_with("requires"))
         .ok_or_else(|| anyhow!("missing requires line"))?;
 
-    Ok((id.into(), parse_requires(requires)?))
+    Ok((id.into(), parse_requires(requires, line)?))
 }
 
 fn parse_building<'a>(
-    lines: &mut impl Iterator<Item = &'a str>,
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    chain: &str,
     header: &'a str,
     buildings: &mut Vec<Building>,
     mode: ParserMode,
@@ -89,16 +100,19 @@ fn parse_building<'a>(
     let mut block = get_block(lines)?.into_iter();
     loop {
         match block.next() {
-            Some(l)
+            Some((i, l))
                 if l.split_whitespace()
                     .nth(0)
                     .map_or(false, |w| levels.contains(&w)) =>
             {
-                let level = parse_level(&mut block, l, mode)
-                    .with_context(|| format!("parsing level {l:?}"))?;
+                let mut level = parse_level(&mut block, l, i, mode)
+                    .with_context(|| format!("parsing level {l:?}"))
+                    .map_err(|e| at_line(e, i, None))?;
+                level.chain = chain.into();
+                level.index = buildings.iter().filter(|b| b.chain == chain).count();
                 buildings.push(level);
             }
-            Some(l) => bail!("unexpected line in levels {l}"),
+            Some((i, l)) => return Err(at_line(anyhow!("unexpected line in levels {l}"), i, None)),
             None => break,
         }
     }
@@ -106,26 +120,51 @@ fn parse_building<'a>(
 }
 
 fn parse_level<'a>(
-    lines: &mut impl Iterator<Item = &'a str>,
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
     header: &'a str,
+    line: usize,
     mode: ParserMode,
 ) -> Result<Building> {
     let mut split = header.split_whitespace();
-    split
+    let name = split
         .next()
         .ok_or_else(|| anyhow!("invalid level header"))?;
     let mut req = split.remainder();
     if mode == Medieval2 && matches!(split.next(), Some("city") | Some("castle")) {
         req = split.remainder();
     }
-    let req = req.map_or(Ok(Requires::None), parse_requires)?;
+    let req = req.map_or(Ok(Requires::None), |r| parse_requires(r, line))?;
     let mut min = "village".into();
     let mut caps = Vec::new();
+    let mut effects = Vec::new();
+    let mut faction_effects = Vec::new();
+    let mut upgrades = Vec::new();
+    let mut cost = 0;
+    let mut turns = 0;
     let mut block = get_block(lines)?.into_iter();
     loop {
         match block.next() {
-            Some("capability") => caps = parse_caps(&mut block, mode)?,
-            Some(l) if l.starts_with("settlement_min") => {
+            Some((_, "capability")) => (caps, effects) = parse_caps(&mut block, mode)?,
+            Some((_, "faction_capability")) => {
+                faction_effects = get_block(&mut block)?
+                    .into_iter()
+                    .map(|(_, l)| normalize(l))
+                    .collect()
+            }
+            Some((_, "upgrades")) => {
+                upgrades = get_block(&mut block)?
+                    .into_iter()
+                    .filter_map(|(_, l)| l.split_whitespace().next())
+                    .map(Into::into)
+                    .collect()
+            }
+            Some((i, l)) if l.split_whitespace().next() == Some("construction") => {
+                turns = parse_amount(l).map_err(|e| at_line(e, i, None))?
+            }
+            Some((i, l)) if l.split_whitespace().next() == Some("cost") => {
+                cost = parse_amount(l).map_err(|e| at_line(e, i, None))?
+            }
+            Some((_, l)) if l.starts_with("settlement_min") => {
                 min = l
                     .split_whitespace()
                     .nth(1)
@@ -136,132 +175,184 @@ fn parse_level<'a>(
             None => break,
         }
     }
-    Ok(Building { req, caps, min })
+    Ok(Building {
+        chain: String::new(),
+        name: name.into(),
+        index: 0,
+        req,
+        caps,
+        effects,
+        faction_effects,
+        upgrades,
+        cost,
+        turns,
+        min,
+        line,
+    })
+}
+
+fn is_recruit(line: &str, mode: ParserMode) -> bool {
+    match line.split_whitespace().next() {
+        Some("recruit") => true,
+        Some("recruit_pool") => mode == Medieval2,
+        _ => false,
+    }
 }
 
+/// Parses a capability block into its recruitment options and its other
+/// effects, which are kept as their text.
 fn parse_caps<'a>(
-    lines: &mut impl Iterator<Item = &'a str>,
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
     mode: ParserMode,
-) -> Result<Vec<RecruitOption>> {
-    get_block(lines)?
+) -> Result<(Vec<RecruitOption>, Vec<String>)> {
+    let (recruits, effects): (Vec<_>, Vec<_>) = get_block(lines)?
+        .into_iter()
+        .partition(|(_, l)| is_recruit(l, mode));
+    let caps = recruits
         .into_iter()
-        .filter(|l| l.starts_with("recruit"))
-        .filter_map(|l| {
-            Some(
-                try {
-                    let mut split = l.split_whitespace();
-                    let kw = split
+        .map(|(i, l)| {
+            let option: Result<RecruitOption> = try {
+                let mut split = l.split_whitespace();
+                let kw = split
+                    .next()
+                    .ok_or_else(|| anyhow!("invalid recruit line"))?;
+                let mut split = split
+                    .remainder()
+                    .ok_or_else(|| anyhow!("missing unit"))?
+                    .split('"');
+                let open_quote = split.next().ok_or_else(|| anyhow!("missing unit"))?;
+                if open_quote != "" {
+                    Err(anyhow!("missing quotes around unit"))?;
+                }
+                let unit = split.next().ok_or_else(|| anyhow!("missing unit"))?;
+                let mut split = split
+                    .remainder()
+                    .ok_or_else(|| anyhow!("missing exp"))?
+                    .trim()
+                    .split_whitespace();
+                if kw == "recruit_pool" {
+                    split
                         .next()
-                        .ok_or_else(|| anyhow!("invalid recruit line"))?;
-                    if kw != "recruit" && (mode != Medieval2 || kw != "recruit_pool") {
-                        return None;
-                    }
-                    let mut split = split
-                        .remainder()
-                        .ok_or_else(|| anyhow!("missing unit"))?
-                        .split('"');
-                    let open_quote = split.next().ok_or_else(|| anyhow!("missing unit"))?;
-                    if open_quote != "" {
-                        Err(anyhow!("missing quotes around unit"))?;
-                    }
-                    let unit = split.next().ok_or_else(|| anyhow!("missing unit"))?;
-                    let mut split = split
-                        .remainder()
-                        .ok_or_else(|| anyhow!("missing exp"))?
-                        .trim()
-                        .split_whitespace();
-                    if kw == "recruit_pool" {
-                        split
-                            .next()
-                            .ok_or_else(|| anyhow!("missing recruit pool data"))?;
-                        split
-                            .next()
-                            .ok_or_else(|| anyhow!("missing recruit pool data"))?;
-                        split
-                            .next()
-                            .ok_or_else(|| anyhow!("missing recruit pool data"))?;
-                    }
-                    let exp = split
+                        .ok_or_else(|| anyhow!("missing recruit pool data"))?;
+                    split
                         .next()
-                        .ok_or_else(|| anyhow!("missing exp"))
-                        .and_then(parse_maybe_float_int)
-                        .with_context(|| format!("parsing exp from {l}"))?;
-                    let req = split
-                        .remainder()
-                        .map_or(Ok(Requires::None), parse_requires)?;
-                    RecruitOption {
-                        unit: unit.trim().into(),
-                        exp,
-                        req,
-                    }
-                },
-            )
+                        .ok_or_else(|| anyhow!("missing recruit pool data"))?;
+                    split
+                        .next()
+                        .ok_or_else(|| anyhow!("missing recruit pool data"))?;
+                }
+                let exp = split
+                    .next()
+                    .ok_or_else(|| anyhow!("missing exp"))
+                    .and_then(parse_maybe_float_int)
+                    .with_context(|| format!("parsing exp from {l}"))?;
+                let req = split
+                    .remainder()
+                    .map_or(Ok(Requires::None), |r| parse_requires(r, i))?;
+                RecruitOption {
+                    unit: unit.trim().into(),
+                    exp,
+                    req,
+                    line: i,
+                }
+            };
+            option.map_err(|e| at_line(e, i, None))
         })
-        .collect()
+        .collect::<Result<_>>()?;
+    Ok((
+        caps,
+        effects.into_iter().map(|(_, l)| normalize(l)).collect(),
+    ))
+}
+
+fn normalize(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn parse_amount(line: &str) -> Result<u32> {
+    line.split_whitespace()
+        .nth(1)
+        .ok_or_else(|| anyhow!("missing value"))
+        .and_then(parse_maybe_float_int)
+        .with_context(|| format!("parsing {line:?}"))
 }
 
-fn parse_requires(requires: &str) -> Result<Requires> {
+fn parse_requires(requires: &str, line: usize) -> Result<Requires> {
     use requires::*;
     let req = Parser::parse(Rule::Requires, requires)
-        .with_context(|| format!("parsing requirement {requires}"))?
+        .map_err(|e| {
+            let pos = match e.location {
+                InputLocation::Pos(pos) => pos,
+                InputLocation::Span((start, _)) => start,
+            };
+            at_line(
+                anyhow!("invalid requirement: {}", e.variant.message()),
+                line,
+                Some(&requires[pos..]),
+            )
+        })?
         .next()
         .unwrap();
 
-    parse_req_op(req.into_inner())
+    parse_req_op(req.into_inner(), line)
 }
 
-fn parse_req_op(mut pairs: Pairs<requires::Rule>) -> Result<Requires> {
+fn parse_req_op(mut pairs: Pairs<requires::Rule>, line: usize) -> Result<Requires> {
     use requires::Rule;
     let pair = pairs.next().unwrap();
     Ok(match pair.as_rule() {
-        Rule::Not => parse_req_not(pair.into_inner())?,
-        Rule::ReqPrimary => parse_req_primary(pair.into_inner())?,
-        Rule::Or => parse_req_or(pair.into_inner())?,
-        Rule::And => parse_req_and(pair.into_inner())?,
+        Rule::Not => parse_req_not(pair.into_inner(), line)?,
+        Rule::ReqPrimary => parse_req_primary(pair.into_inner(), line)?,
+        Rule::Or => parse_req_or(pair.into_inner(), line)?,
+        Rule::And => parse_req_and(pair.into_inner(), line)?,
         _ => bail!("unexpected parse result {pair}, expected `req_op`"),
     })
 }
 
-fn parse_req_or(pairs: Pairs<requires::Rule>) -> Result<Requires> {
+fn parse_req_or(pairs: Pairs<requires::Rule>, line: usize) -> Result<Requires> {
     use requires::Rule;
     Ok(Requires::Or(
         pairs
             .map(|p| match p.as_rule() {
-                Rule::Not => parse_req_not(p.into_inner()),
-                Rule::ReqPrimary => parse_req_primary(p.into_inner()),
-                Rule::Or => parse_req_or(p.into_inner()),
-                Rule::And => parse_req_and(p.into_inner()),
+                Rule::Not => parse_req_not(p.into_inner(), line),
+                Rule::ReqPrimary => parse_req_primary(p.into_inner(), line),
+                Rule::Or => parse_req_or(p.into_inner(), line),
+                Rule::And => parse_req_and(p.into_inner(), line),
                 _ => bail!("unexpected parse result {p}, expected `req_or` child"),
             })
             .collect::<Result<_>>()?,
     ))
 }
 
-fn parse_req_and(pairs: Pairs<requires::Rule>) -> Result<Requires> {
+fn parse_req_and(pairs: Pairs<requires::Rule>, line: usize) -> Result<Requires> {
     use requires::Rule;
     Ok(Requires::And(
         pairs
             .map(|p| match p.as_rule() {
-                Rule::Not => parse_req_not(p.into_inner()),
-                Rule::ReqPrimary => parse_req_primary(p.into_inner()),
-                Rule::Or => parse_req_or(p.into_inner()),
-                Rule::And => parse_req_and(p.into_inner()),
+                Rule::Not => parse_req_not(p.into_inner(), line),
+                Rule::ReqPrimary => parse_req_primary(p.into_inner(), line),
+                Rule::Or => parse_req_or(p.into_inner(), line),
+                Rule::And => parse_req_and(p.into_inner(), line),
                 _ => bail!("unexpected parse result {p}, expected `req_and` child"),
             })
             .collect::<Result<_>>()?,
     ))
 }
 
-fn parse_req_not(mut pairs: Pairs<requires::Rule>) -> Result<Requires> {
+fn parse_req_not(mut pairs: Pairs<requires::Rule>, line: usize) -> Result<Requires> {
     Ok(Requires::Not(Box::new(parse_req_primary(
         pairs.next().unwrap().into_inner(),
+        line,
     )?)))
 }
-fn parse_req_primary(mut pairs: Pairs<requires::Rule>) -> Result<Requires> {
+fn parse_req_primary(mut pairs: Pairs<requires::Rule>, line: usize) -> Result<Requires> {
     use requires::Rule;
     let pair = pairs.next().unwrap();
     Ok(match pair.as_rule() {
-        Rule::Unknown => Requires::Unknown,
+        Rule::Unknown => Requires::Unknown {
+            text: pair.as_str().into(),
+            line,
+        },
         Rule::Alias => Requires::Alias(pair.as_str().into()),
         Rule::Resource => parse_resource(pair.into_inner())?,
         Rule::HiddenResource => parse_hidden_resource(pair.into_inner())?,
@@ -423,11 +514,11 @@ fn parse_no_tag(mut pairs: Pairs<requires::Rule>) -> Result<Requires> {
     })
 }
 
-fn skip_block<'a>(lines: &mut impl Iterator<Item = &'a str>) {
+fn skip_block<'a>(lines: &mut impl Iterator<Item = (usize, &'a str)>) {
     let mut braces = 0;
     loop {
         match lines.next() {
-            Some(line) => {
+            Some((_, line)) => {
                 braces += line.chars().filter(|c| *c == OPEN_BRACE).count();
                 braces -= line.chars().filter(|c| *c == CLOSE_BRACE).count();
                 if braces == 0 {
@@ -439,17 +530,19 @@ fn skip_block<'a>(lines: &mut impl Iterator<Item = &'a str>) {
     }
 }
 
-fn get_block<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Result<Vec<&'a str>> {
+fn get_block<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Result<Vec<(usize, &'a str)>> {
     let mut braces = 0;
     let mut block = Vec::new();
     loop {
         match lines.next() {
-            Some("{") if braces == 0 => braces += 1,
-            Some("}") if braces == 1 => return Ok(block),
-            Some(line) => {
+            Some((_, "{")) if braces == 0 => braces += 1,
+            Some((_, "}")) if braces == 1 => return Ok(block),
+            Some((i, line)) => {
                 braces += line.chars().filter(|c| *c == OPEN_BRACE).count();
                 braces -= line.chars().filter(|c| *c == CLOSE_BRACE).count();
-                block.push(line);
+                block.push((i, line));
                 if braces == 0 {
                     return Ok(block);
                 }
@@ -462,18 +555,32 @@ fn get_block<'a>(lines: &mut impl Iterator<Item = &'a str>) -> Result<Vec<&'a st
 const OPEN_BRACE: char = '{';
 const CLOSE_BRACE: char = '}';
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Building {
+    pub chain: String,
+    pub name: String,
+    /// The position of this level in its chain, starting at 0.
+    pub index: usize,
     pub req: Requires,
     pub caps: Vec<RecruitOption>,
+    /// The capabilities other than recruitment, such as `law_bonus bonus 1`.
+    pub effects: Vec<String>,
+    /// The capabilities that apply to the whole faction (Medieval II only).
+    pub faction_effects: Vec<String>,
+    /// The levels this level can be upgraded to.
+    pub upgrades: Vec<String>,
+    pub cost: u32,
+    pub turns: u32,
     pub min: String,
+    pub line: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RecruitOption {
     pub unit: String,
     pub exp: u32,
     pub req: Requires,
+    pub line: usize,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -528,13 +635,119 @@ pub enum Requires {
 
     Alias(String),
 
-    Unknown,
+    Unknown {
+        text: String,
+        line: usize,
+    },
 
     Not(Box<Requires>),
     And(Vec<Requires>),
     Or(Vec<Requires>),
 }
 
+impl Building {
+    /// A short label for this level, such as "barracks (level 3, city+)".
+    pub fn label(&self) -> String {
+        match self.min.as_str() {
+            "" | "village" => format!("{} (level {})", self.chain, self.index + 1),
+            min => format!("{} (level {}, {min}+)", self.chain, self.index + 1),
+        }
+    }
+}
+
+fn scope(queued: bool, factionwide: bool) -> &'static str {
+    match (queued, factionwide) {
+        (false, false) => "",
+        (true, false) => " (or queued)",
+        (false, true) => " anywhere in the faction",
+        (true, true) => " (or queued) anywhere in the faction",
+    }
+}
+
+/// Renders requirements as English, for requirements that went through
+/// [`simplify`](crate::parse::eval::simplify).
+impl Display for Requires {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Requires::None => write!(f, "always"),
+            Requires::False => write!(f, "never"),
+            Requires::Resource { id, factionwide } => {
+                write!(f, "with resource {id}{}", scope(false, *factionwide))
+            }
+            Requires::HiddenResource { id, factionwide } => {
+                let scope = scope(false, *factionwide);
+                write!(f, "in a region with hidden_resource {id}{scope}")
+            }
+            Requires::BuildingPresent {
+                id,
+                level,
+                queued,
+                factionwide,
+            } => write!(
+                f,
+                "with {}{}",
+                level.as_ref().unwrap_or(id),
+                scope(*queued, *factionwide)
+            ),
+            Requires::MajorEvent(event) => write!(f, "after {event}"),
+            Requires::EventCount { event, count } => write!(f, "after {event} {count} times"),
+            Requires::Factions(factions) => write!(f, "for {}", factions.join(", ")),
+            Requires::BuildingFactions(factions) => {
+                write!(f, "in buildings built by {}", factions.join(", "))
+            }
+            Requires::Port => write!(f, "in a port"),
+            Requires::IsPlayer => write!(f, "for the player"),
+            Requires::IsToggled(toggle) => write!(f, "when {toggle} is on"),
+            Requires::Diplomacy { status, faction } => write!(f, "{status} {faction}"),
+            Requires::Capability { capability, amount } => {
+                write!(f, "with {capability} {amount}")
+            }
+            Requires::NoBuildingTagged {
+                tag,
+                queued,
+                factionwide,
+            } => write!(
+                f,
+                "without a building tagged {tag}{}",
+                scope(*queued, *factionwide)
+            ),
+            Requires::Religion { id, cmp, amount } => write!(f, "with {cmp} {amount}% {id}"),
+            Requires::MajorityReligion(id) => write!(f, "where {id} is the majority religion"),
+            Requires::OfficialReligion => write!(f, "where the official religion is the majority"),
+            Requires::Alias(id) => write!(f, "{id}"),
+            Requires::Unknown { text, .. } => write!(f, "{text}"),
+            Requires::Not(req) => match req.as_ref() {
+                Requires::MajorEvent(event) => write!(f, "before {event}"),
+                req => write!(f, "not ({req})"),
+            },
+            Requires::And(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    match item {
+                        Requires::Or(_) => write!(f, "({item})")?,
+                        _ => write!(f, "{item}")?,
+                    }
+                }
+                Ok(())
+            }
+            Requires::Or(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " or ")?;
+                    }
+                    match item {
+                        Requires::And(_) => write!(f, "({item})")?,
+                        _ => write!(f, "{item}")?,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Cmp {
     Lt,
@@ -566,6 +779,28 @@ impl FromStr for Cmp {
     }
 }
 
+impl Cmp {
+    pub fn compare(&self, left: u32, right: u32) -> bool {
+        match self {
+            Cmp::Lt => left < right,
+            Cmp::Le => left <= right,
+            Cmp::Gt => left > right,
+            Cmp::Ge => left >= right,
+        }
+    }
+}
+
+impl Display for Cmp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Cmp::Lt => write!(f, "under"),
+            Cmp::Le => write!(f, "at most"),
+            Cmp::Gt => write!(f, "over"),
+            Cmp::Ge => write!(f, "at least"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum DipStatus {
     Allied,
@@ -598,3 +833,15 @@ impl FromStr for DipStatus {
         }
     }
 }
+
+impl Display for DipStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DipStatus::Allied => write!(f, "allied with"),
+            DipStatus::Protector => write!(f, "protector of"),
+            DipStatus::Protectorate => write!(f, "protectorate of"),
+            DipStatus::SameSuperfaction => write!(f, "in the same superfaction as"),
+            DipStatus::AtWar => write!(f, "at war with"),
+        }
+    }
+}
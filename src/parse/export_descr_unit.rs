@@ -2,32 +2,44 @@ use std::{collections::HashMap, str::pattern::Pattern};
 
 use anyhow::{Context as _, Result, anyhow};
 
-use crate::{parse::manifest::ParserMode, utils::parse_maybe_float_int};
+use crate::{
+    parse::{diagnostic::at_line, manifest::ParserMode},
+    utils::parse_maybe_float_int,
+};
 
 pub fn parse(data: impl AsRef<str>, _: ParserMode) -> Result<Vec<Unit>> {
     data.as_ref()
         .lines() // split lines
-        .filter_map(|l| l.split(';').nth(0)) // strip comments
-        .map(|l| l.trim()) // strip leading/trailing whitespace
-        .filter(|l| l.len() > 0) // strip empty lines
-        .fold(vec![], |mut acc: Vec<Vec<String>>, line| {
+        .enumerate() // number lines
+        .filter_map(|(i, l)| l.split(';').nth(0).map(|l| (i + 1, l))) // strip comments
+        .map(|(i, l)| (i, l.trim())) // strip leading/trailing whitespace
+        .filter(|(_, l)| l.len() > 0) // strip empty lines
+        .fold(vec![], |mut acc: Vec<Vec<(usize, String)>>, (i, line)| {
             if line.starts_with("type") {
-                acc.push(vec![line.into()]);
+                acc.push(vec![(i, line.into())]);
             } else {
                 let idx = acc.len() - 1;
-                acc[idx].push(line.into());
+                acc[idx].push((i, line.into()));
             }
             acc
         })
         .into_iter()
-        .map(|s| parse_unit(&s).with_context(|| format!("parsing unit: {s:?}")))
+        .map(|s| parse_unit(&s).map_err(|e| at_line(e, s[0].0, None)))
         .collect()
 }
 
-fn parse_unit(lines: &[String]) -> Result<Unit> {
-    let raw: Vec<_> = lines
+fn parse_unit(numbered_lines: &[(usize, String)]) -> Result<Unit> {
+    let lines: HashMap<_, _> = numbered_lines
         .iter()
-        .map(|line| {
+        .filter_map(|(i, line)| {
+            line.split(char::is_whitespace)
+                .next()
+                .map(|k| (k.into(), *i))
+        })
+        .collect();
+    let raw: Vec<_> = numbered_lines
+        .iter()
+        .map(|(_, line)| {
             let mut split = line.split(char::is_whitespace);
             let keyword = split
                 .next()
@@ -44,11 +56,25 @@ fn parse_unit(lines: &[String]) -> Result<Unit> {
         category: require_line_value(&entries, "category")?.into(),
         class: require_line_value(&entries, "class")?.into(),
         ownership: parse_ownership(require_line_value(&entries, "ownership")?),
-        stats: parse_statblock(&entries, &raw)?,
+        stats: parse_statblock(&entries, &lines, &raw)?,
+        line: numbered_lines[0].0,
+        lines,
     })
 }
 
-fn parse_statblock(entries: &UnitEntries, raw: &[(&str, Option<&str>)]) -> Result<StatBlock> {
+fn parse_statblock(
+    entries: &UnitEntries,
+    lines: &HashMap<String, usize>,
+    raw: &[(&str, Option<&str>)],
+) -> Result<StatBlock> {
+    let at = |keyword: &'static str| {
+        let line = lines.get(keyword).copied();
+        let value = get_line_value(entries, keyword);
+        move |e: anyhow::Error| match line {
+            Some(line) => at_line(e, line, value),
+            None => e,
+        }
+    };
     let soldier_line = split_line(entries, "soldier", OPT_COMMA);
     let soldiers_line = split_line(entries, "soldiers", OPT_COMMA);
     let attribute_line = split_line(entries, "attributes", COMMA)?;
@@ -67,7 +93,9 @@ fn parse_statblock(entries: &UnitEntries, raw: &[(&str, Option<&str>)]) -> Resul
 
     let speed_mod: f64 = require_line_value(entries, "move_speed_mod")
         .unwrap_or("1.0")
-        .parse()?;
+        .parse()
+        .context("parsing speed modifier")
+        .map_err(at("move_speed_mod"))?;
 
     Ok(StatBlock {
         soldier_model: if let Ok(l) = &soldier_line {
@@ -91,7 +119,12 @@ fn parse_statblock(entries: &UnitEntries, raw: &[(&str, Option<&str>)]) -> Resul
         } else {
             Err(anyhow!("missing soldier/soldiers info"))
         }
-        .context("parsing # of soldiers")?,
+        .context("parsing # of soldiers")
+        .map_err(at(if entries.contains_key("soldier") {
+            "soldier"
+        } else {
+            "soldiers"
+        }))?,
         officers: raw.iter().filter(|(s, _)| *s == "officer").count() as _,
         mount: get_line_value(entries, "mount").map(Into::into),
         attributes: attribute_line
@@ -99,73 +132,87 @@ fn parse_statblock(entries: &UnitEntries, raw: &[(&str, Option<&str>)]) -> Resul
             .copied()
             .map(parse_attribute)
             .collect::<Result<_>>()
-            .with_context(|| format!("parsing attributes from {attribute_line:?}"))?,
+            .with_context(|| format!("parsing attributes from {attribute_line:?}"))
+            .map_err(at("attributes"))?,
         speed_mod,
         formations: formations_line[5..]
             .into_iter()
             .copied()
             .map(|s| Ok(s.parse()?))
             .collect::<Result<_>>()
-            .with_context(|| format!("parsing formations from {formations_line:?}"))?,
+            .with_context(|| format!("parsing formations from {formations_line:?}"))
+            .map_err(at("formation"))?,
         hp: health_line
             .get(0)
             .copied()
             .ok_or_else(|| anyhow!("missing hit points"))
             .and_then(parse_maybe_float_int)
-            .with_context(|| format!("parsing hit points from {health_line:?}"))?,
+            .with_context(|| format!("parsing hit points from {health_line:?}"))
+            .map_err(at("stat_health"))?,
         hp_mount: health_line
             .get(1)
             .copied()
             .and_then(|s| parse_maybe_float_int(s).ok())
             .unwrap_or(1),
-        primary_weapon: parse_weapon(&pri_line, &pri_attr_line).with_context(|| {
-            format!("parsing primary weapon from {pri_line:?}, {pri_attr_line:?}")
-        })?,
-        secondary_weapon: parse_weapon(&sec_line, &sec_attr_line).with_context(|| {
-            format!("parsing secondary weapon from {sec_line:?}, {sec_attr_line:?}")
-        })?,
+        primary_weapon: parse_weapon(&pri_line, &pri_attr_line)
+            .with_context(|| format!("parsing primary weapon from {pri_line:?}, {pri_attr_line:?}"))
+            .map_err(at("stat_pri"))?,
+        secondary_weapon: parse_weapon(&sec_line, &sec_attr_line)
+            .with_context(|| {
+                format!("parsing secondary weapon from {sec_line:?}, {sec_attr_line:?}")
+            })
+            .map_err(at("stat_sec"))?,
         defense: parse_defense(&pri_armour_line)
-            .with_context(|| format!("parsing defense from {pri_armour_line:?}"))?,
+            .with_context(|| format!("parsing defense from {pri_armour_line:?}"))
+            .map_err(at("stat_pri_armour"))?,
         defense_mount: parse_defense(&sec_armour_line)
-            .with_context(|| format!("parsing mount defense from {sec_armour_line:?}"))?,
+            .with_context(|| format!("parsing mount defense from {sec_armour_line:?}"))
+            .map_err(at("stat_sec_armour"))?,
         heat: heat_line
             .get(0)
             .copied()
             .ok_or_else(|| anyhow!("missing heat bonus/penalty"))
             .and_then(parse_maybe_float_int)
-            .with_context(|| format!("parsing heat bonus/penalty from {heat_line:?}"))?,
+            .with_context(|| format!("parsing heat bonus/penalty from {heat_line:?}"))
+            .map_err(at("stat_heat"))?,
         ground_bonus: parse_ground(&ground_line)
-            .with_context(|| format!("parsing ground bonuses from {ground_line:?}"))?,
+            .with_context(|| format!("parsing ground bonuses from {ground_line:?}"))
+            .map_err(at("stat_ground"))?,
         morale: mental_line
             .get(0)
             .copied()
             .ok_or_else(|| anyhow!("missing morale"))
             .and_then(parse_maybe_float_int)
-            .with_context(|| format!("parsing morale from {mental_line:?}"))?,
+            .with_context(|| format!("parsing morale from {mental_line:?}"))
+            .map_err(at("stat_mental"))?,
         discipline: mental_line
             .get(1)
             .copied()
             .ok_or_else(|| anyhow!("missing morale"))
             .and_then(|s| Ok(s.parse()?))
-            .with_context(|| format!("parsing discipline from {mental_line:?}"))?,
+            .with_context(|| format!("parsing discipline from {mental_line:?}"))
+            .map_err(at("stat_mental"))?,
         turns: cost_line
             .get(0)
             .copied()
             .ok_or_else(|| anyhow!("missing build turns"))
             .and_then(parse_maybe_float_int)
-            .with_context(|| format!("parsing build turns from {cost_line:?}"))?,
+            .with_context(|| format!("parsing build turns from {cost_line:?}"))
+            .map_err(at("stat_cost"))?,
         cost: cost_line
             .get(1)
             .copied()
             .ok_or_else(|| anyhow!("missing cost"))
             .and_then(parse_maybe_float_int)
-            .with_context(|| format!("parsing cost from {cost_line:?}"))?,
+            .with_context(|| format!("parsing cost from {cost_line:?}"))
+            .map_err(at("stat_cost"))?,
         upkeep: cost_line
             .get(2)
             .copied()
             .ok_or_else(|| anyhow!("missing upkeep"))
             .and_then(parse_maybe_float_int)
-            .with_context(|| format!("parsing upkeep from {cost_line:?}"))?,
+            .with_context(|| format!("parsing upkeep from {cost_line:?}"))
+            .map_err(at("stat_cost"))?,
     })
 }
 
@@ -285,7 +332,7 @@ fn parse_weapon_attribute(string: &str) -> Result<WeaponAttr> {
         s if s.starts_with("spear_bonus_") => {
             WeaponAttr::SpearBonus(s["spear_bonus_".len()..].parse()?)
         }
-        _ => WeaponAttr::Unknown,
+        s => WeaponAttr::Unknown(s.into()),
     })
 }
 
@@ -343,7 +390,7 @@ fn parse_attribute(string: &str) -> Result<Attr> {
                 .unwrap_or("marian_reforms");
             Attr::GeneralUnitUpgrade(event.into())
         }
-        _ => Attr::Unknown,
+        s => Attr::Unknown(s.into()),
     })
 }
 
@@ -380,7 +427,7 @@ fn require_line_value<'a>(entries: &'a UnitEntries, key: &str) -> Result<&'a str
 const OPT_COMMA: &[char] = &[',', ' '];
 const COMMA: &str = ",";
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Unit {
     pub id: String,
     pub key: String,
@@ -388,9 +435,19 @@ pub struct Unit {
     pub class: String,
     pub stats: StatBlock,
     pub ownership: Vec<String>,
+    /// The line of the `type` keyword that starts the entry.
+    pub line: usize,
+    /// The line of each keyword in the entry.
+    pub lines: HashMap<String, usize>,
+}
+
+impl Unit {
+    pub fn line_of(&self, keyword: &str) -> usize {
+        self.lines.get(keyword).copied().unwrap_or(self.line)
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StatBlock {
     pub soldier_model: String,
     pub speed_mod: f64,
@@ -414,7 +471,7 @@ pub struct StatBlock {
     pub upkeep: u32,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Weapon {
     pub factor: u32,
     pub charge: u32,
@@ -447,7 +504,7 @@ pub type GroundBonus = silphium::model::GroundBonus;
 
 pub type Defense = silphium::model::Defense;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Attr {
     SeaFaring,
     HideForest,
@@ -491,13 +548,13 @@ pub enum Attr {
 
     UiOrAiHint,
 
-    Unknown,
+    Unknown(String),
 }
 
 pub type Formation = silphium::model::Formation;
 pub type Discipline = silphium::model::Discipline;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum WeaponAttr {
     ArmorPiercing,
     BodyPiercing,
@@ -511,5 +568,5 @@ pub enum WeaponAttr {
     Area,
     SpearBonus(u32),
     Fire,
-    Unknown,
+    Unknown(String),
 }
@@ -16,6 +16,8 @@ pub struct Manifest {
     pub mode: ParserMode,
     #[serde(default)]
     pub dir: Option<PathBuf>,
+    #[serde(default)]
+    pub roots: Vec<PathBuf>,
     #[serde(default = "default_campaign")]
     pub campaign: String,
     #[serde(default = "default_banner")]
@@ -35,7 +37,9 @@ pub struct Manifest {
     #[serde(default)]
     pub pools: Vec<IString>,
     #[serde(default)]
-    pub aors: Vec<IString>,
+    pub aors: AorNames,
+    #[serde(default)]
+    pub languages: IndexMap<IString, LanguageSpec>,
 
     #[serde(skip, default)]
     pub raw: String,
@@ -60,6 +64,46 @@ pub struct EraSpec {
     pub evaluator: Evaluator,
 }
 
+/// Names for the areas of recruitment, either in order or keyed by the name
+/// generated from their regions.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum AorNames {
+    List(Vec<IString>),
+    Map(HashMap<IString, IString>),
+}
+
+impl Default for AorNames {
+    fn default() -> Self {
+        Self::List(vec![])
+    }
+}
+
+impl AorNames {
+    pub fn get(&self, index: usize, generated: &str) -> Option<IString> {
+        match self {
+            Self::List(names) => names.get(index).cloned(),
+            Self::Map(names) => names.get(generated).cloned(),
+        }
+    }
+}
+
+/// A language the mod is translated in. The first one listed is the language
+/// of the site root, the others get their own pages under their id.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LanguageSpec {
+    pub name: IString,
+    #[serde(default = "default_text")]
+    pub text: PathBuf,
+    #[serde(default)]
+    pub messages: Option<PathBuf>,
+}
+
+fn default_text() -> PathBuf {
+    "data/text".into()
+}
+
 fn default_campaign() -> String {
     "imperial_campaign".into()
 }
@@ -82,3 +126,21 @@ impl Manifest {
         Ok(serde_yml::from_reader(r).context("parsing manifest")?)
     }
 }
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SiteManifest {
+    pub modules: Vec<PathBuf>,
+}
+
+impl SiteManifest {
+    pub fn from_yaml(r: impl io::Read) -> Result<Option<SiteManifest>> {
+        let value: serde_yml::Value = serde_yml::from_reader(r).context("parsing manifest")?;
+        if value.get("modules").is_none() {
+            return Ok(None);
+        }
+        Ok(Some(
+            serde_yml::from_value(value).context("parsing site manifest")?,
+        ))
+    }
+}
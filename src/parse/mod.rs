@@ -1,13 +1,14 @@
 use std::{collections::HashMap, path::PathBuf, time::Duration};
 
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use implicit_clone::unsync::IString;
+use indexmap::IndexMap;
 use indicatif::{MultiProgress, ProgressBar};
 use silphium::{
-    ModuleMap,
+    Messages, ModuleMap,
     model::{Era, Module},
 };
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::{
     args::Config,
@@ -16,12 +17,13 @@ use crate::{
         descr_mercenaries::Pool,
         descr_model_battle::Model,
         descr_mount::Mount,
+        diagnostic::in_file,
         export_descr_buildings::{Building, Requires},
         manifest::ParserMode::{self, *},
-        model::{ModelBits, RawModel, build_model},
+        model::{ModelBits, RawModel, Texts, build_model},
     },
     render::RenderData,
-    utils::{LOOKING_GLASS, THINKING, progress_style, read_file},
+    utils::{LOOKING_GLASS, THINKING, decode_image, progress_style, read_file},
 };
 
 mod descr_cultures;
@@ -36,35 +38,70 @@ mod export_descr_unit;
 mod sd;
 mod text;
 
+mod diagnostic;
 mod eval;
 mod model;
 
 pub mod manifest;
 
 pub use descr_regions::Region;
+pub use diagnostic::Diagnostic;
 pub use manifest::Manifest;
+pub use model::Translation;
 pub use sd::Sprite;
 
+pub async fn parse_site(cfgs: &[Config]) -> Result<(ModuleMap, HashMap<IString, RenderData>)> {
+    let mut module_map = ModuleMap::new();
+    let mut render_data = HashMap::new();
+    for cfg in cfgs {
+        let (modules, extra) = parse_folder(cfg)
+            .await
+            .with_context(|| format!("parsing module {}", cfg.manifest.id))?;
+        module_map.extend(modules);
+        render_data.extend(extra);
+    }
+    Ok((module_map, render_data))
+}
+
+pub async fn check_site(cfgs: &[Config]) -> Result<Vec<Diagnostic>> {
+    let mut diagnostics = vec![];
+    for cfg in cfgs {
+        let (_, _, found) = parse_folder_diagnostics(cfg)
+            .await
+            .with_context(|| format!("parsing module {}", cfg.manifest.id))?;
+        diagnostics.extend(found);
+    }
+    Ok(diagnostics)
+}
+
 pub async fn parse_folder(cfg: &Config) -> Result<(ModuleMap, HashMap<IString, RenderData>)> {
+    let (module_map, render_data, diagnostics) = parse_folder_diagnostics(cfg).await?;
+    for d in diagnostics {
+        info!("{d}");
+    }
+    Ok((module_map, render_data))
+}
+
+async fn parse_folder_diagnostics(
+    cfg: &Config,
+) -> Result<(ModuleMap, HashMap<IString, RenderData>, Vec<Diagnostic>)> {
     let m = MultiProgress::new();
 
     let folder = ModFolder::new(cfg.clone());
 
-    let text_expanded_txt = folder.text_expanded_txt();
-    let mut text = parse_progress(
-        m.clone(),
-        text_expanded_txt.clone(),
-        parse_text(cfg, text_expanded_txt, cfg.manifest.mode),
-    )
-    .await?;
-    let text_export_units_txt = folder.text_export_units_txt();
-    let export_units = parse_progress(
-        m.clone(),
-        text_export_units_txt.clone(),
-        parse_text(cfg, text_export_units_txt, cfg.manifest.mode),
-    )
-    .await?;
-    text.extend(export_units.into_iter());
+    let messages = parse_messages(cfg).await?;
+    let messages_in = |id: &IString| messages.get(id).cloned().unwrap_or_default();
+    let first = match cfg.manifest.languages.first() {
+        Some((id, _)) => messages_in(id),
+        None => Messages::default(),
+    };
+    let texts = parse_texts(cfg, &folder, first, m.clone()).await?;
+    let mut translated = IndexMap::new();
+    for (id, language) in cfg.manifest.languages.iter().skip(1) {
+        let folder = folder.in_language(language);
+        let texts = parse_texts(cfg, &folder, messages_in(id), m.clone()).await?;
+        translated.insert(id.clone(), texts);
+    }
 
     let descr_mercenaries_txt = folder.descr_mercenaries_txt();
     let pools = parse_progress(
@@ -80,6 +117,13 @@ pub async fn parse_folder(cfg: &Config) -> Result<(ModuleMap, HashMap<IString, R
         parse_descr_regions(cfg, descr_regions_txt, cfg.manifest.mode),
     )
     .await?;
+    let map_regions_tga = folder.map_regions_tga();
+    let region_sizes = parse_progress(
+        m.clone(),
+        map_regions_tga.clone(),
+        parse_region_sizes(cfg, map_regions_tga, &regions),
+    )
+    .await?;
     let descr_sm_factions_txt = folder.descr_sm_factions_txt();
     let factions = parse_progress(
         m.clone(),
@@ -141,95 +185,177 @@ pub async fn parse_folder(cfg: &Config) -> Result<(ModuleMap, HashMap<IString, R
     pb.set_style(progress_style());
     pb.set_message(format!("{THINKING}building catalog..."));
     pb.enable_steady_tick(Duration::from_millis(200));
-    let aliases = cfg
-        .manifest
-        .aliases
-        .iter()
-        .map(|(k, v)| (k.clone(), v.clone()))
-        .collect();
-    let ModelBits {
+    let mut diagnostics = vec![];
+    let raw = RawModel {
+        units,
         factions,
         regions,
         pools,
+        buildings,
+        require_aliases,
+        texts,
+        translated,
+        region_sizes,
+        strat,
+        mounts,
+        models,
         sprites,
-        culture,
-    } = build_model(
-        &cfg,
-        RawModel {
-            units,
-            factions,
-            regions,
-            pools,
-            buildings,
-            require_aliases,
-            text,
-            strat,
-            mounts,
-            models,
-            sprites,
-            default_culture,
-        },
-    );
-
-    let module_map = ModuleMap::from([(
-        cfg.manifest.id.clone(),
-        Module {
-            id: cfg.manifest.id.clone(),
-            name: cfg.manifest.name.clone(),
-            banner: cfg.manifest.banner.to_string_lossy().into_owned().into(),
-            factions,
-            pools,
-            aliases,
-            eras: cfg
-                .manifest
-                .eras
-                .iter()
-                .map(|(id, v)| {
-                    (
-                        id.clone(),
-                        Era {
-                            id: id.clone(),
-                            icon: v
-                                .icon
-                                .clone()
-                                .unwrap_or_else(|| {
-                                    PathBuf::from("eras")
-                                        .join(id.as_ref())
-                                        .with_added_extension("png")
-                                })
-                                .to_string_lossy()
-                                .into_owned()
-                                .into(),
-                            icoff: v
-                                .icoff
-                                .clone()
-                                .unwrap_or_else(|| {
-                                    PathBuf::from("eras").join(format!("{id}-off.png"))
-                                })
-                                .to_string_lossy()
-                                .into_owned()
-                                .into(),
-                            name: v.name.clone().unwrap_or(id.clone()),
-                        },
-                    )
-                })
-                .collect(),
-        },
-    )]);
-
+        default_culture,
+    };
+    let (module, render_data) = build_module(cfg, build_model(cfg, raw, &mut diagnostics));
+    let module_map = ModuleMap::from([(cfg.manifest.id.clone(), module)]);
     let render_data = HashMap::from([(
         cfg.manifest.id.clone(),
         RenderData {
-            regions,
-            sprites,
-            culture,
+            messages,
+            ..render_data
         },
     )]);
 
     info!("built catalog");
     let _ = m.clear();
 
-    Ok((module_map, render_data))
+    Ok((module_map, render_data, diagnostics))
+}
+
+/// The module built from the catalog model, with what is needed to render its
+/// images.
+fn build_module(cfg: &Config, bits: ModelBits) -> (Module, RenderData) {
+    let ModelBits {
+        factions,
+        regions,
+        pools,
+        descriptions,
+        buildings,
+        requires,
+        require_aliases,
+        catalog_regions,
+        sprites,
+        culture,
+        translations,
+    } = bits;
+    let aliases = cfg
+        .manifest
+        .aliases
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    let module = Module {
+        id: cfg.manifest.id.clone(),
+        name: cfg.manifest.name.clone(),
+        banner: cfg.manifest.banner.to_string_lossy().into_owned().into(),
+        factions,
+        pools,
+        aliases,
+        eras: cfg
+            .manifest
+            .eras
+            .iter()
+            .map(|(id, v)| {
+                (
+                    id.clone(),
+                    Era {
+                        id: id.clone(),
+                        icon: v
+                            .icon
+                            .clone()
+                            .unwrap_or_else(|| {
+                                PathBuf::from("eras")
+                                    .join(id.as_ref())
+                                    .with_added_extension("png")
+                            })
+                            .to_string_lossy()
+                            .into_owned()
+                            .into(),
+                        icoff: v
+                            .icoff
+                            .clone()
+                            .unwrap_or_else(|| PathBuf::from("eras").join(format!("{id}-off.png")))
+                            .to_string_lossy()
+                            .into_owned()
+                            .into(),
+                        name: v.name.clone().unwrap_or(id.clone()),
+                    },
+                )
+            })
+            .collect(),
+        descriptions,
+        buildings,
+        regions: catalog_regions,
+        map: None,
+        requires,
+        require_aliases,
+    };
+    let render_data = RenderData {
+        regions,
+        sprites,
+        culture,
+        translations,
+        ..Default::default()
+    };
+    (module, render_data)
+}
+
+/// The text files of the mod in one language: the names and descriptions of
+/// everything, and the names of regions and settlements.
+async fn parse_texts(
+    cfg: &Config,
+    folder: &ModFolder,
+    messages: Messages,
+    m: MultiProgress,
+) -> Result<Texts> {
+    let text_expanded_txt = folder.text_expanded_txt();
+    let mut text = parse_progress(
+        m.clone(),
+        text_expanded_txt.clone(),
+        parse_text(cfg, text_expanded_txt, cfg.manifest.mode),
+    )
+    .await?;
+    let text_export_units_txt = folder.text_export_units_txt();
+    let export_units = parse_progress(
+        m.clone(),
+        text_export_units_txt.clone(),
+        parse_text(cfg, text_export_units_txt, cfg.manifest.mode),
+    )
+    .await?;
+    text.extend(export_units.into_iter());
+    let text_export_buildings_txt = folder.text_export_buildings_txt();
+    let export_buildings = parse_progress(
+        m.clone(),
+        text_export_buildings_txt.clone(),
+        parse_text(cfg, text_export_buildings_txt, cfg.manifest.mode),
+    )
+    .await?;
+    text.extend(export_buildings.into_iter());
+    let text_region_names_txt = folder.text_regions_and_settlement_names_txt();
+    let region_names = parse_progress(
+        m.clone(),
+        text_region_names_txt.clone(),
+        parse_text(cfg, text_region_names_txt, cfg.manifest.mode),
+    )
+    .await?;
+    Ok(Texts {
+        text,
+        region_names,
+        messages,
+    })
+}
+
+/// The translations of the site texts for each language of the mod that has
+/// a messages file.
+async fn parse_messages(cfg: &Config) -> Result<HashMap<IString, Messages>> {
+    let mut messages = HashMap::new();
+    for (id, language) in cfg.manifest.languages.iter() {
+        let Some(ref path) = language.messages else {
+            continue;
+        };
+        let path = cfg.manifest_dir.join(path);
+        let buf = read_file(cfg, &path).await?;
+        let table = serde_yml::from_slice(&buf)
+            .with_context(|| format!("parsing messages {}", path.display()))?;
+        messages.insert(id.clone(), Messages(table));
+    }
+    Ok(messages)
 }
 
 fn parse_progress<'a, T>(
@@ -257,14 +383,23 @@ async fn parse_text(
     mut path: PathBuf,
     mode: ParserMode,
 ) -> Result<HashMap<String, String>> {
-    if mode == Medieval2 && !path.exists() {
+    if mode == Medieval2
+        && cfg.files.find(&path).is_none()
+        && cfg.packs.find(&cfg.fallback_dir, &path).is_none()
+    {
         path.add_extension("strings.bin");
         let buf = read_file(cfg, &path).await?;
         text::parse_bin(buf, mode)
     } else {
         let buf = read_file(cfg, &path).await?;
-        let data = String::from_utf16le_lossy(&buf).replace(BOM, "");
-        text::parse_txt(data, mode)
+        let (data, guessed) = text::decode(&buf);
+        if let Some(encoding) = guessed {
+            warn!(
+                "{} has no byte order mark, reading it as {encoding}",
+                path.display()
+            );
+        }
+        text::parse_txt(data.replace(BOM, ""), mode)
     }
 }
 
@@ -275,13 +410,39 @@ async fn parse_descr_mercenaries(
 ) -> Result<Vec<Pool>> {
     let buf = read_file(cfg, &path).await?;
     let data = String::from_utf8_lossy(&buf);
-    descr_mercenaries::parse(data, mode)
+    descr_mercenaries::parse(&data, mode).map_err(|e| in_file(e, &path, &data))
 }
 
 async fn parse_descr_regions(cfg: &Config, path: PathBuf, mode: ParserMode) -> Result<Vec<Region>> {
     let buf = read_file(cfg, &path).await?;
     let data = String::from_utf8_lossy(&buf);
-    descr_regions::parse(data, mode)
+    descr_regions::parse(&data, mode).map_err(|e| in_file(e, &path, &data))
+}
+
+/// The number of pixels of each region on the region map, found by its
+/// color.
+async fn parse_region_sizes(
+    cfg: &Config,
+    path: PathBuf,
+    regions: &[Region],
+) -> Result<HashMap<String, usize>> {
+    if regions.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let buf = read_file(cfg, &path).await?;
+    let areas = decode_image(&path, buf)?.into_rgb8();
+    let mut counts: HashMap<[u8; 3], usize> = HashMap::new();
+    for pixel in areas.pixels() {
+        *counts.entry(pixel.0).or_default() += 1;
+    }
+    Ok(regions
+        .iter()
+        .map(|r| {
+            let (red, green, blue) = r.color;
+            let size = counts.get(&[red, green, blue]).copied().unwrap_or_default();
+            (r.id.clone(), size)
+        })
+        .collect())
 }
 
 async fn parse_descr_sm_factions(
@@ -301,7 +462,7 @@ async fn parse_export_descr_unit(
 ) -> Result<Vec<export_descr_unit::Unit>> {
     let buf = read_file(cfg, &path).await?;
     let data = String::from_utf8_lossy(&buf);
-    export_descr_unit::parse(data, mode)
+    export_descr_unit::parse(&data, mode).map_err(|e| in_file(e, &path, &data))
 }
 
 async fn parse_export_descr_buildings(
@@ -311,7 +472,7 @@ async fn parse_export_descr_buildings(
 ) -> Result<(HashMap<String, Requires>, Vec<Building>)> {
     let buf = read_file(cfg, &path).await?;
     let data = String::from_utf8_lossy(&buf);
-    export_descr_buildings::parse(data, mode)
+    export_descr_buildings::parse(&data, mode).map_err(|e| in_file(e, &path, &data))
 }
 
 async fn parse_descr_strat(
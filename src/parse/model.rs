@@ -1,18 +1,24 @@
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    path::Path,
+};
 
 use implicit_clone::unsync::{IArray, IString};
 use indexmap::IndexMap;
-use silphium::model;
+use silphium::{Messages, model};
 
 use crate::{
     args::Config,
+    mod_folder::ModFolder,
     parse::{
+        Diagnostic,
         descr_mercenaries::Pool,
         descr_model_battle::Model,
         descr_mount::{Mount, MountClass},
-        descr_regions::Region,
+        descr_regions::{Region, majority_religion},
         descr_sm_factions,
-        eval::{Evaluator, evaluate},
+        eval::{Evaluator, evaluate, simplify},
         export_descr_buildings::{Building, Requires},
         export_descr_unit::{self, Attr, WeaponAttr},
         manifest::ParserMode::*,
@@ -20,6 +26,7 @@ use crate::{
     },
 };
 
+#[derive(Clone)]
 pub struct RawModel {
     pub units: Vec<export_descr_unit::Unit>,
     pub factions: Vec<descr_sm_factions::Faction>,
@@ -27,7 +34,12 @@ pub struct RawModel {
     pub pools: Vec<Pool>,
     pub buildings: Vec<Building>,
     pub require_aliases: HashMap<String, Requires>,
-    pub text: HashMap<String, String>,
+    /// The texts in the first language of the mod.
+    pub texts: Texts,
+    /// The texts in the other languages of the mod, keyed by language.
+    pub translated: IndexMap<IString, Texts>,
+    /// The number of pixels of each region on the region map.
+    pub region_sizes: HashMap<String, usize>,
     pub strat: HashMap<String, usize>,
     pub mounts: HashMap<String, Mount>,
     pub models: HashMap<String, Model>,
@@ -35,6 +47,148 @@ pub struct RawModel {
     pub default_culture: String,
 }
 
+/// The texts of the mod in one language.
+#[derive(Clone, Default)]
+pub struct Texts {
+    /// The names and descriptions of units, factions and buildings.
+    pub text: HashMap<String, String>,
+    /// The names of regions and settlements.
+    pub region_names: HashMap<String, String>,
+    /// The translations of the site texts.
+    pub messages: Messages,
+}
+
+impl Texts {
+    /// The text for a key, whatever its case.
+    fn get(&self, key: &str) -> Option<String> {
+        self.text
+            .get(&key.to_lowercase())
+            .map(|t| t.trim().to_string())
+    }
+
+    /// The name of a unit or faction, or its key if it has none.
+    fn name(&self, key: &str) -> String {
+        self.get(key).unwrap_or_else(|| key.trim().to_string())
+    }
+
+    /// The display name of a region or settlement, or its id if it has none.
+    fn region_name(&self, id: &str) -> String {
+        self.region_names
+            .get(&id.to_lowercase())
+            .map(|name| name.trim().to_string())
+            .unwrap_or_else(|| id.to_string())
+    }
+}
+
+/// The texts of a catalog in another language, to put in place of those of
+/// the catalog built in the first language.
+#[derive(Clone)]
+pub struct Translation {
+    factions: HashMap<IString, IString>,
+    units: HashMap<IString, IString>,
+    descriptions: IndexMap<IString, model::Description>,
+    levels: HashMap<IString, (IString, IString)>,
+    regions: HashMap<IString, (IString, IString)>,
+    aors: Vec<(IArray<IString>, IString)>,
+}
+
+impl Translation {
+    /// The module with its texts in this language.
+    pub fn apply(&self, module: &model::Module) -> model::Module {
+        let unit = |u: model::Unit| model::Unit {
+            name: self.units.get(&u.key).cloned().unwrap_or(u.name.clone()),
+            ..u
+        };
+        let factions = module
+            .factions
+            .iter()
+            .map(|(id, f)| {
+                let faction = model::Faction {
+                    name: self.factions.get(id).cloned().unwrap_or(f.name.clone()),
+                    roster: f.roster.iter().map(&unit).collect(),
+                    aors: f
+                        .aors
+                        .iter()
+                        .map(|aor| match aor_named(&self.aors, &aor.regions) {
+                            Some(name) => model::Aor { name, ..aor },
+                            None => aor,
+                        })
+                        .collect(),
+                    ..f.clone()
+                };
+                (id.clone(), faction)
+            })
+            .collect();
+        let pools = module
+            .pools
+            .iter()
+            .map(|p| model::Pool {
+                units: p
+                    .units
+                    .iter()
+                    .map(|e| model::PoolEntry {
+                        unit: unit(e.unit.clone()),
+                        ..e
+                    })
+                    .collect(),
+                ..p
+            })
+            .collect();
+        let buildings = module
+            .buildings
+            .iter()
+            .map(|c| model::BuildingChain {
+                levels: c
+                    .levels
+                    .iter()
+                    .map(|l| {
+                        let (name, description) = self
+                            .levels
+                            .get(&l.id)
+                            .cloned()
+                            .unwrap_or((l.name.clone(), l.description.clone()));
+                        let recruits = l
+                            .recruits
+                            .iter()
+                            .map(|r| model::Recruit {
+                                name: self.units.get(&r.unit).cloned().unwrap_or(r.name.clone()),
+                                ..r
+                            })
+                            .collect();
+                        model::BuildingLevel {
+                            name,
+                            description,
+                            recruits,
+                            ..l
+                        }
+                    })
+                    .collect(),
+                ..c
+            })
+            .collect();
+        let regions = module
+            .regions
+            .iter()
+            .map(|r| match self.regions.get(&r.id) {
+                Some((name, settlement)) => model::Region {
+                    name: name.clone(),
+                    settlement: settlement.clone(),
+                    ..r
+                },
+                None => r,
+            })
+            .collect();
+        model::Module {
+            factions,
+            pools,
+            descriptions: self.descriptions.clone(),
+            buildings,
+            regions,
+            ..module.clone()
+        }
+    }
+}
+
 struct IntermediateModel {
     unit_map: IndexMap<String, export_descr_unit::Unit>,
     factions: Vec<descr_sm_factions::Faction>,
@@ -42,28 +196,47 @@ struct IntermediateModel {
     pools: Vec<Pool>,
     buildings: Vec<Building>,
     require_aliases: HashMap<String, Requires>,
-    text: HashMap<String, String>,
+    texts: Texts,
+    region_sizes: HashMap<String, usize>,
     strat: HashMap<String, usize>,
     mounts: HashMap<String, Mount>,
     models: HashMap<String, Model>,
     sprites: HashMap<String, Sprite>,
     default_culture: String,
+    recruitment: HashMap<String, Vec<Recruitment>>,
     requires: HashMap<String, Requires>,
     tech_levels: HashMap<String, u32>,
+    /// The evaluators of the eras, with the building levels resolved.
+    eras: IndexMap<IString, Evaluator>,
 }
 
 pub struct ModelBits {
     pub factions: IndexMap<IString, model::Faction>,
     pub pools: IArray<model::Pool>,
+    pub descriptions: IndexMap<IString, model::Description>,
+    pub buildings: IArray<model::BuildingChain>,
+    pub requires: HashMap<IString, model::Requirement>,
+    pub require_aliases: HashMap<IString, model::Requirement>,
+    pub catalog_regions: IArray<model::Region>,
     pub regions: HashMap<String, Region>,
     pub sprites: HashMap<String, Sprite>,
     pub culture: String,
+    pub translations: IndexMap<IString, Translation>,
 }
 
-pub fn build_model(cfg: &Config, raw: RawModel) -> ModelBits {
+pub fn build_model(cfg: &Config, raw: RawModel, diagnostics: &mut Vec<Diagnostic>) -> ModelBits {
+    let folder = ModFolder::new(cfg.clone());
     let unit_map: IndexMap<_, _> = raw.units.into_iter().map(|u| (u.id.clone(), u)).collect();
-    let requires = build_requires(&raw.buildings, &unit_map);
+    let translated = raw.translated;
+    let recruitment = build_recruitment(&raw.buildings, &unit_map);
+    let requires = build_requires(&recruitment);
     let tech_levels = build_tech_levels(&raw.buildings);
+    let eras = cfg
+        .manifest
+        .eras
+        .iter()
+        .map(|(id, e)| (id.clone(), e.evaluator.with_levels(&raw.buildings)))
+        .collect();
     let mut raw = IntermediateModel {
         unit_map,
         factions: raw.factions,
@@ -71,15 +244,19 @@ pub fn build_model(cfg: &Config, raw: RawModel) -> ModelBits {
         pools: raw.pools,
         buildings: raw.buildings,
         require_aliases: raw.require_aliases,
-        text: raw.text,
+        texts: raw.texts,
+        region_sizes: raw.region_sizes,
         strat: raw.strat,
         mounts: raw.mounts,
         models: raw.models,
         sprites: raw.sprites,
         default_culture: raw.default_culture,
+        recruitment,
         requires,
         tech_levels,
+        eras,
     };
+    check_model(&raw, &folder, diagnostics);
 
     let regions = raw
         .regions
@@ -87,7 +264,7 @@ pub fn build_model(cfg: &Config, raw: RawModel) -> ModelBits {
         .cloned()
         .map(|r| (r.id.clone(), r))
         .collect();
-    let pools = raw
+    let pools: IArray<model::Pool> = raw
         .pools
         .iter()
         .enumerate()
@@ -98,21 +275,304 @@ pub fn build_model(cfg: &Config, raw: RawModel) -> ModelBits {
         .extract_if(.., |f| {
             !raw.strat.contains_key(&f.id) || cfg.manifest.exclude.contains(&f.id)
         })
-        .count();
+        .filter(|f| !cfg.manifest.exclude.contains(&f.id))
+        .for_each(|f| {
+            diagnostics.push(Diagnostic::new(
+                folder.descr_sm_factions_txt(),
+                f.line,
+                format!(
+                    "faction {} is missing from descr_strat.txt and is left out",
+                    f.id
+                ),
+            ))
+        });
     raw.factions.sort_by_key(|f| raw.strat[&f.id]);
 
-    let factions = raw
+    let mut factions: IndexMap<_, _> = raw
         .factions
         .iter()
         .map(|f| build_faction(f, cfg, &raw))
         .collect();
+    let aor_names = aor_names(&factions, cfg, &raw, &raw.texts);
+    name_aors(&mut factions, &aor_names);
+
+    let rosters = factions
+        .values()
+        .flat_map(|f| f.roster.iter().map(move |u| (f.id.clone(), u)));
+    let mercs = pools.iter().flat_map(|p| {
+        p.units
+            .iter()
+            .map(|e| (IString::from("mercs"), e.unit))
+            .collect::<Vec<_>>()
+    });
+    let mut checked = HashSet::new();
+    for (faction, unit) in rosters.chain(mercs) {
+        if checked.insert((faction.clone(), unit.id.clone()))
+            && !folder.has_unit_info_tga(&faction, &unit.key)
+        {
+            diagnostics.push(Diagnostic::new(
+                folder.export_descr_unit_txt(),
+                raw.unit_map[unit.id.as_str()].line,
+                format!(
+                    "no unit card for {} in {faction}, generic_unit_card.tga is used",
+                    unit.key
+                ),
+            ));
+        }
+    }
+
+    let descriptions = build_descriptions(&factions, &pools, &raw.texts);
+    let buildings = build_buildings(&factions, &raw);
+    let requires = factions
+        .values()
+        .flat_map(|f| f.roster.iter())
+        .filter_map(|u| {
+            let req = raw.requires.get(u.id.as_str())?;
+            Some((u.id.clone(), build_requirement(req, &raw.buildings)))
+        })
+        .collect();
+    let require_aliases = raw
+        .require_aliases
+        .iter()
+        .map(|(id, req)| (id.clone().into(), build_requirement(req, &raw.buildings)))
+        .collect();
+    let catalog_regions = raw
+        .regions
+        .iter()
+        .map(|r| model::Region {
+            id: r.id.clone().into(),
+            name: raw.texts.region_name(&r.id).into(),
+            settlement: raw.texts.region_name(&r.city).into(),
+            hidden_resources: r
+                .hidden_resources
+                .iter()
+                .cloned()
+                .map(IString::from)
+                .collect(),
+            units: build_region_units(r, &factions, &raw),
+            pools: pools
+                .iter()
+                .filter(|p| p.regions.iter().any(|id| id.as_str() == r.id))
+                .map(|p| p.id.clone())
+                .collect(),
+            outline: IString::default(),
+        })
+        .collect();
+    let translations = translated
+        .iter()
+        .map(|(id, texts)| (id.clone(), translate(&factions, &pools, cfg, &raw, texts)))
+        .collect();
 
     ModelBits {
         factions,
         pools,
+        descriptions,
+        buildings,
+        requires,
+        require_aliases,
+        catalog_regions,
         regions,
         sprites: raw.sprites,
         culture: raw.default_culture,
+        translations,
+    }
+}
+
+/// The texts of the catalog in another language.
+fn translate(
+    factions: &IndexMap<IString, model::Faction>,
+    pools: &IArray<model::Pool>,
+    cfg: &Config,
+    raw: &IntermediateModel,
+    texts: &Texts,
+) -> Translation {
+    Translation {
+        factions: raw
+            .factions
+            .iter()
+            .map(|f| (f.id.clone().into(), texts.name(&f.name).into()))
+            .collect(),
+        units: raw
+            .unit_map
+            .values()
+            .map(|u| (u.key.clone().into(), texts.name(&u.key).into()))
+            .collect(),
+        descriptions: build_descriptions(factions, pools, texts),
+        levels: raw
+            .buildings
+            .iter()
+            .map(|b| {
+                let (name, description) = level_texts(b, &raw.default_culture, texts);
+                (b.name.clone().into(), (name.into(), description.into()))
+            })
+            .collect(),
+        regions: raw
+            .regions
+            .iter()
+            .map(|r| {
+                let name = texts.region_name(&r.id).into();
+                let settlement = texts.region_name(&r.city).into();
+                (r.id.clone().into(), (name, settlement))
+            })
+            .collect(),
+        aors: aor_names(factions, cfg, raw, texts),
+    }
+}
+
+fn build_descriptions(
+    factions: &IndexMap<IString, model::Faction>,
+    pools: &IArray<model::Pool>,
+    texts: &Texts,
+) -> IndexMap<IString, model::Description> {
+    let text = |key: String| -> IString { texts.get(&key).map(IString::from).unwrap_or_default() };
+    let rosters = factions.values().flat_map(|f| f.roster.iter());
+    let mercs = pools
+        .iter()
+        .flat_map(|p| p.units.iter().map(|e| e.unit).collect::<Vec<_>>());
+    let mut descriptions = IndexMap::new();
+    for unit in rosters.chain(mercs) {
+        if descriptions.contains_key(&unit.key) {
+            continue;
+        }
+        let key = unit.key.to_lowercase();
+        let description = model::Description {
+            long: text(format!("{key}_descr")),
+            short: text(format!("{key}_descr_short")),
+        };
+        if !description.long.is_empty() || !description.short.is_empty() {
+            descriptions.insert(unit.key.clone(), description);
+        }
+    }
+    descriptions
+}
+
+fn build_buildings(
+    factions: &IndexMap<IString, model::Faction>,
+    raw: &IntermediateModel,
+) -> IArray<model::BuildingChain> {
+    let evaluators: Vec<_> = raw
+        .factions
+        .iter()
+        .filter(|f| factions.contains_key(f.id.as_str()))
+        .map(|f| (IString::from(f.id.clone()), Evaluator::faction(f)))
+        .collect();
+
+    let mut chains: IndexMap<&str, Vec<model::BuildingLevel>> = IndexMap::new();
+    for b in raw.buildings.iter() {
+        let recruits = b
+            .caps
+            .iter()
+            .filter_map(|cap| {
+                let unit = raw.unit_map.get(&cap.unit)?;
+                let req = Requires::And(vec![
+                    cap.req.clone(),
+                    b.req.clone(),
+                    require_ownership(unit),
+                ]);
+                let factions: IArray<IString> = evaluators
+                    .iter()
+                    .filter(|(id, eval)| {
+                        factions[id].roster.iter().any(|u| u.id == unit.id)
+                            && !matches!(
+                                simplify(&req, &raw.require_aliases, eval),
+                                Requires::False
+                            )
+                    })
+                    .map(|(id, _)| id.clone())
+                    .collect();
+                Some(model::Recruit {
+                    unit: unit.key.clone().into(),
+                    name: raw.texts.name(&unit.key).into(),
+                    factions,
+                })
+            })
+            .collect();
+        let capabilities = b
+            .effects
+            .iter()
+            .cloned()
+            .chain(
+                b.faction_effects
+                    .iter()
+                    .map(|e| format!("{e} (faction-wide)")),
+            )
+            .map(IString::from)
+            .collect();
+        let (name, description) = level_texts(b, &raw.default_culture, &raw.texts);
+        let level = model::BuildingLevel {
+            id: b.name.clone().into(),
+            name: name.into(),
+            description: description.into(),
+            min: b.min.clone().into(),
+            cost: b.cost,
+            turns: b.turns,
+            upgrades: b.upgrades.iter().cloned().map(IString::from).collect(),
+            capabilities,
+            recruits,
+        };
+        chains.entry(&b.chain).or_default().push(level);
+    }
+    chains
+        .into_iter()
+        .map(|(id, levels)| model::BuildingChain {
+            id: id.to_string().into(),
+            levels: levels.into(),
+        })
+        .collect()
+}
+
+/// The name and description of a building level, or those of its version for
+/// a culture.
+fn level_texts(b: &Building, culture: &str, texts: &Texts) -> (String, String) {
+    let name = texts
+        .get(&b.name)
+        .or_else(|| texts.get(&format!("{}_{culture}", b.name)))
+        .unwrap_or(b.name.clone());
+    let description = texts
+        .get(&format!("{}_desc", b.name))
+        .or_else(|| texts.get(&format!("{}_{culture}_desc", b.name)))
+        .unwrap_or_default();
+    (name, description)
+}
+
+/// Converts a requirement for the catalog, keeping the conditions that can be
+/// explored in the browser.
+fn build_requirement(req: &Requires, buildings: &[Building]) -> model::Requirement {
+    let build_all = |items: &Vec<Requires>| {
+        items
+            .iter()
+            .map(|r| build_requirement(r, buildings))
+            .collect()
+    };
+    match req {
+        Requires::None => model::Requirement::None,
+        Requires::False => model::Requirement::False,
+        Requires::HiddenResource {
+            id,
+            factionwide: false,
+        } => model::Requirement::HiddenResource(id.clone().into()),
+        Requires::MajorEvent(event) => model::Requirement::MajorEvent(event.clone().into()),
+        Requires::BuildingPresent { id, level, .. } => {
+            let find_level = |name: &str| buildings.iter().find(|b| b.name == name);
+            let present = match level {
+                Some(level) => find_level(level).map(|b| (b.chain.as_str(), b.index)),
+                None if buildings.iter().any(|b| &b.chain == id) => Some((id.as_str(), 0)),
+                None => find_level(id).map(|b| (b.chain.as_str(), b.index)),
+            };
+            present.map_or(model::Requirement::Unknown, |(chain, index)| {
+                model::Requirement::BuildingPresent(chain.to_string().into(), index as u32)
+            })
+        }
+        Requires::Factions(factions) => {
+            model::Requirement::Factions(factions.iter().cloned().map(IString::from).collect())
+        }
+        Requires::Port => model::Requirement::Port,
+        Requires::IsPlayer => model::Requirement::IsPlayer,
+        Requires::Alias(id) => model::Requirement::Alias(id.clone().into()),
+        Requires::Not(req) => model::Requirement::Not(Box::new(build_requirement(req, buildings))),
+        Requires::And(items) => model::Requirement::And(build_all(items)),
+        Requires::Or(items) => model::Requirement::Or(build_all(items)),
+        _ => model::Requirement::Unknown,
     }
 }
 
@@ -134,7 +594,11 @@ fn build_faction(
             )
         })
         .map(|u| {
-            let u = build_unit(u, cfg, raw);
+            let recruitment = describe_recruitment(&u.id, f, raw);
+            let u = model::Unit {
+                recruitment,
+                ..build_unit(u, cfg, raw)
+            };
             if u.horde {
                 is_horde = true;
             }
@@ -147,18 +611,12 @@ fn build_faction(
         f.id.clone().into(),
         model::Faction {
             id: f.id.clone().into(),
-            name: raw
-                .text
-                .get(&f.name.to_lowercase())
-                .cloned()
-                .unwrap_or(f.name.clone())
-                .trim()
-                .to_string()
-                .into(),
+            name: raw.texts.name(&f.name).into(),
             image: match cfg.manifest.mode {
                 Original | Remastered => f.logo_path.to_str().unwrap().to_string().into(),
                 Medieval2 => f.logo_index.clone().into(),
             },
+            culture: f.culture.clone().into(),
             alias: cfg
                 .manifest
                 .aliases
@@ -293,14 +751,7 @@ fn build_unit(u: &export_descr_unit::Unit, cfg: &Config, raw: &IntermediateModel
     model::Unit {
         id,
         key: u.key.clone().into(),
-        name: raw
-            .text
-            .get(&u.key.to_lowercase())
-            .cloned()
-            .unwrap_or(u.key.clone())
-            .trim()
-            .to_string()
-            .into(),
+        name: raw.texts.name(&u.key).into(),
         class,
         image: Default::default(),
         soldiers: u.stats.soldiers,
@@ -325,14 +776,13 @@ fn build_unit(u: &export_descr_unit::Unit, cfg: &Config, raw: &IntermediateModel
         cost: u.stats.cost,
         upkeep: u.stats.upkeep,
         eras: {
-            cfg.manifest
-                .eras
+            raw.eras
                 .iter()
-                .filter_map(|(id, e)| {
+                .filter_map(|(id, eval)| {
                     if evaluate(
                         raw.requires.get(&u.id).unwrap_or(&Requires::False),
                         &raw.require_aliases,
-                        &e.evaluator,
+                        eval,
                     ) {
                         Some(id.clone())
                     } else {
@@ -354,6 +804,7 @@ fn build_unit(u: &export_descr_unit::Unit, cfg: &Config, raw: &IntermediateModel
         is_unique,
 
         is_regional: false,
+        recruitment: Default::default(),
 
         move_speed,
 
@@ -375,24 +826,20 @@ fn build_pool(p: &Pool, index: usize, cfg: &Config, raw: &IntermediateModel) ->
         units: p
             .units
             .iter()
-            .map(|e| {
-                let u = &raw
-                    .unit_map
-                    .get(&e.id)
-                    .expect(&format!("missing unit {:?}", e.id));
+            .filter_map(|e| {
+                let u = raw.unit_map.get(&e.id)?;
                 let mut unit = build_unit(u, cfg, raw);
                 unit.cost = e.cost;
-                model::PoolEntry {
+                Some(model::PoolEntry {
                     unit,
                     exp: e.exp,
                     replenish: e.replenish.into(),
                     max: e.max,
                     initial: e.initial,
                     restrict: e.restrict.iter().map(|s| s.clone().into()).collect(),
-                }
+                })
             })
             .collect(),
-        map: format!("pool-{}", index + 1).into(),
     }
 }
 
@@ -662,10 +1109,17 @@ fn build_tech_levels(raw_buildings: &Vec<Building>) -> HashMap<String, u32> {
     map
 }
 
-fn build_requires(
+/// One way of recruiting a unit: a building level, or the bodyguard and horde
+/// rules that need no building.
+struct Recruitment {
+    source: String,
+    req: Requires,
+}
+
+fn build_recruitment(
     raw_buildings: &Vec<Building>,
     unit_map: &IndexMap<String, export_descr_unit::Unit>,
-) -> HashMap<String, Requires> {
+) -> HashMap<String, Vec<Recruitment>> {
     let general_events: HashSet<_> = unit_map
         .values()
         .filter_map(|u| general_upgrade_event(u))
@@ -675,44 +1129,165 @@ fn build_requires(
     raw_buildings
         .into_iter()
         .flat_map(|b| {
-            b.caps.iter().map(move |r| {
-                let owners = require_ownership(
-                    &unit_map
-                        .get(&r.unit)
-                        .expect(&format!("missing unit {:?}", r.unit)),
-                );
-                (
+            b.caps.iter().filter_map(move |r| {
+                let owners = require_ownership(unit_map.get(&r.unit)?);
+                Some((
                     r.unit.clone(),
-                    Requires::And(vec![r.req.clone(), b.req.clone(), owners]),
-                )
+                    Recruitment {
+                        source: b.label(),
+                        req: Requires::And(vec![r.req.clone(), b.req.clone(), owners]),
+                    },
+                ))
             })
         })
         .chain(unit_map.values().filter(|&u| is_general(u)).map(|u| {
             (
                 u.id.clone(),
-                Requires::And(vec![
-                    require_ownership(u),
-                    general_upgrade_event(u)
-                        .map(|e| Requires::MajorEvent(e.clone()))
-                        .unwrap_or(unupgraded_requires.clone()),
-                ]),
+                Recruitment {
+                    source: "general's bodyguard".into(),
+                    req: Requires::And(vec![
+                        require_ownership(u),
+                        general_upgrade_event(u)
+                            .map(|e| Requires::MajorEvent(e.clone()))
+                            .unwrap_or(unupgraded_requires.clone()),
+                    ]),
+                },
+            )
+        }))
+        .chain(unit_map.values().filter(|&u| can_horde(u)).map(|u| {
+            (
+                u.id.clone(),
+                Recruitment {
+                    source: "as a horde".into(),
+                    req: require_ownership(u),
+                },
             )
         }))
-        .chain(
-            unit_map
-                .values()
-                .filter(|&u| can_horde(u))
-                .map(|u| (u.id.clone(), require_ownership(u))),
-        )
         .fold(HashMap::new(), |mut h, (u, r)| {
-            match h.entry(u).or_insert(Requires::Or(vec![])) {
-                Requires::Or(items) => items.push(r),
-                _ => unreachable!(),
-            }
+            h.entry(u).or_insert_with(Vec::new).push(r);
             h
         })
 }
 
+fn build_requires(recruitment: &HashMap<String, Vec<Recruitment>>) -> HashMap<String, Requires> {
+    recruitment
+        .iter()
+        .map(|(u, rs)| {
+            (
+                u.clone(),
+                Requires::Or(rs.iter().map(|r| r.req.clone()).collect()),
+            )
+        })
+        .collect()
+}
+
+/// Lists the ways a faction recruits a unit, with what the faction alone
+/// decides already resolved, such as "barracks (level 3, city+) after marian_reforms".
+fn describe_recruitment(
+    unit: &str,
+    faction: &descr_sm_factions::Faction,
+    raw: &IntermediateModel,
+) -> IArray<IString> {
+    let eval = Evaluator::faction(faction);
+    let mut lines: Vec<IString> = vec![];
+    for r in raw.recruitment.get(unit).into_iter().flatten() {
+        let line = match simplify(&r.req, &raw.require_aliases, &eval) {
+            Requires::False => continue,
+            Requires::None => r.source.clone(),
+            req => format!("{} {req}", r.source),
+        };
+        if !lines.iter().any(|l| *l == line) {
+            lines.push(line.into());
+        }
+    }
+    lines.into()
+}
+
+/// Records the problems that the model builder works around instead of failing on.
+fn check_model(raw: &IntermediateModel, folder: &ModFolder, diagnostics: &mut Vec<Diagnostic>) {
+    let edu = folder.export_descr_unit_txt();
+    for u in raw.unit_map.values() {
+        for attr in u.stats.attributes.iter() {
+            if let Attr::Unknown(a) = attr {
+                diagnostics.push(Diagnostic::new(
+                    &edu,
+                    u.line_of("attributes"),
+                    format!("unknown attribute {a:?} in unit {}", u.id),
+                ));
+            }
+        }
+        for (weapon, keyword) in [
+            (&u.stats.primary_weapon, "stat_pri_attr"),
+            (&u.stats.secondary_weapon, "stat_sec_attr"),
+        ] {
+            for attr in weapon.attributes.iter() {
+                if let WeaponAttr::Unknown(a) = attr {
+                    diagnostics.push(Diagnostic::new(
+                        &edu,
+                        u.line_of(keyword),
+                        format!("unknown weapon attribute {a:?} in unit {}", u.id),
+                    ));
+                }
+            }
+        }
+        if !raw.texts.text.contains_key(&u.key.to_lowercase()) {
+            diagnostics.push(Diagnostic::new(
+                &edu,
+                u.line_of("dictionary"),
+                format!("no text for {} in export_units.txt", u.key),
+            ));
+        }
+    }
+
+    let edb = folder.export_descr_buildings_txt();
+    for req in raw.require_aliases.values() {
+        check_requires(req, &edb, diagnostics);
+    }
+    for b in raw.buildings.iter() {
+        check_requires(&b.req, &edb, diagnostics);
+        for cap in b.caps.iter() {
+            check_requires(&cap.req, &edb, diagnostics);
+            if !raw.unit_map.contains_key(&cap.unit) {
+                diagnostics.push(Diagnostic::new(
+                    &edb,
+                    cap.line,
+                    format!("unit {} is missing from export_descr_unit.txt", cap.unit),
+                ));
+            }
+        }
+    }
+
+    let mercenaries = folder.descr_mercenaries_txt();
+    for p in raw.pools.iter() {
+        for e in p.units.iter() {
+            if !raw.unit_map.contains_key(&e.id) {
+                diagnostics.push(Diagnostic::new(
+                    &mercenaries,
+                    e.line,
+                    format!("unit {} is missing from export_descr_unit.txt", e.id),
+                ));
+            }
+        }
+    }
+}
+
+fn check_requires(req: &Requires, path: &Path, diagnostics: &mut Vec<Diagnostic>) {
+    match req {
+        Requires::Unknown { text, line } => diagnostics.push(Diagnostic::new(
+            path,
+            *line,
+            format!("unrecognized requirement {text:?}"),
+        )),
+        Requires::Not(req) => check_requires(req, path, diagnostics),
+        Requires::And(items) | Requires::Or(items) => {
+            for req in items {
+                check_requires(req, path, diagnostics);
+            }
+        }
+        _ => {}
+    }
+}
+
 fn available_to_faction(
     req: &Requires,
     faction: &descr_sm_factions::Faction,
@@ -730,6 +1305,31 @@ fn available_in_region(
     evaluate(req, aliases, &Evaluator::region(region, faction))
 }
 
+/// The units each faction can recruit in a region.
+fn build_region_units(
+    region: &Region,
+    factions: &IndexMap<IString, model::Faction>,
+    raw: &IntermediateModel,
+) -> IndexMap<IString, IArray<IString>> {
+    raw.factions
+        .iter()
+        .filter_map(|f| {
+            let faction = factions.get(f.id.as_str())?;
+            let units: IArray<_> = faction
+                .roster
+                .iter()
+                .filter(|u| {
+                    raw.requires.get(u.id.as_str()).is_none_or(|req| {
+                        available_in_region(req, region, Some(f), &raw.require_aliases)
+                    })
+                })
+                .map(|u| u.id.clone())
+                .collect();
+            (!units.is_empty()).then(|| (faction.id.clone(), units))
+        })
+        .collect()
+}
+
 fn calculate_aors<'a>(
     faction: &descr_sm_factions::Faction,
     roster: &mut [model::Unit],
@@ -773,7 +1373,7 @@ fn calculate_aors<'a>(
         .collect();
 
     // Collect all units in each minimal AOR
-    let mut aor_units = HashMap::new();
+    let mut aor_units = BTreeMap::new();
     for aor in minimal_aors {
         for u in roster.iter_mut() {
             if let Some(u_aor) = unit_aors.get(u.id.as_str())
@@ -790,10 +1390,112 @@ fn calculate_aors<'a>(
 
     aor_units
         .into_iter()
-        .map(|(aor, units)| model::Aor {
-            units: units.into_iter().collect(),
-            regions: aor.into_iter().map(|s| s.to_string().into()).collect(),
-            ..Default::default()
+        .map(|(aor, units)| {
+            let regions: IArray<IString> = aor.iter().map(|s| s.to_string().into()).collect();
+            model::Aor {
+                name: aor_name(&regions, raw, &raw.texts).into(),
+                units: units.into_iter().collect(),
+                religions: aor_religions(&aor, raw),
+                regions,
+            }
         })
         .collect()
 }
+
+/// A name for an AOR made from its regions: the words all their names start
+/// with, or else the names of the largest few.
+fn aor_name(regions: &[IString], raw: &IntermediateModel, texts: &Texts) -> String {
+    let mut regions: Vec<_> = regions.iter().map(|r| r.as_str()).collect();
+    regions.sort_by_key(|id| Reverse(raw.region_sizes.get(*id).copied().unwrap_or_default()));
+    let names: Vec<_> = regions.iter().map(|id| texts.region_name(id)).collect();
+    if names.len() > 1 {
+        let words: Vec<Vec<_>> = names
+            .iter()
+            .map(|n| n.split_whitespace().collect())
+            .collect();
+        let prefix: Vec<_> = words[0]
+            .iter()
+            .enumerate()
+            .take_while(|(i, w)| words.iter().all(|ws| ws.get(*i) == Some(*w)))
+            .map(|(_, w)| *w)
+            .collect();
+        if !prefix.is_empty() {
+            return prefix.join(" ");
+        }
+    }
+    let t = &texts.messages;
+    match names.as_slice() {
+        [] => String::new(),
+        [a] => a.clone(),
+        [a, b] => t.format("{} and {}", &[a, b]),
+        [a, b, c] => t.format("{}, {} and {}", &[a, b, c]),
+        [a, b, c, rest @ ..] => t.format("{}, {}, {} and {} more", &[a, b, c, &rest.len()]),
+    }
+}
+
+/// The names of the distinct AORs of the factions in a language, in the order
+/// they first appear: the names from the manifest, given in that order or
+/// keyed by the names generated in the first language, or else the names
+/// generated in the language.
+fn aor_names(
+    factions: &IndexMap<IString, model::Faction>,
+    cfg: &Config,
+    raw: &IntermediateModel,
+    texts: &Texts,
+) -> Vec<(IArray<IString>, IString)> {
+    let mut names: Vec<(IArray<IString>, IString)> = vec![];
+    for aor in factions.values().flat_map(|f| f.aors.iter()) {
+        if aor_named(&names, &aor.regions).is_some() {
+            continue;
+        }
+        let generated = aor_name(&aor.regions, raw, &raw.texts);
+        let name = cfg
+            .manifest
+            .aors
+            .get(names.len(), &generated)
+            .unwrap_or_else(|| aor_name(&aor.regions, raw, texts).into());
+        names.push((aor.regions, name));
+    }
+    names
+}
+
+/// The name given to the AOR with these regions.
+fn aor_named(names: &[(IArray<IString>, IString)], regions: &IArray<IString>) -> Option<IString> {
+    names
+        .iter()
+        .find(|(r, _)| r == regions)
+        .map(|(_, name)| name.clone())
+}
+
+/// Gives the AORs of the factions their names.
+fn name_aors(
+    factions: &mut IndexMap<IString, model::Faction>,
+    names: &[(IArray<IString>, IString)],
+) {
+    for f in factions.values_mut() {
+        f.aors = f
+            .aors
+            .iter()
+            .map(|aor| match aor_named(names, &aor.regions) {
+                Some(name) => model::Aor { name, ..aor },
+                None => aor,
+            })
+            .collect();
+    }
+}
+
+/// The majority religions of the regions in an AOR, most common first.
+fn aor_religions(aor: &BTreeSet<&str>, raw: &IntermediateModel) -> IArray<IString> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for region in raw.regions.iter().filter(|r| aor.contains(r.id.as_str())) {
+        if let Some(religion) = majority_religion(&region.religions) {
+            *counts.entry(religion).or_default() += 1;
+        }
+    }
+    let mut religions: Vec<_> = counts.into_iter().collect();
+    religions.sort_by_key(|(_, count)| Reverse(*count));
+    religions
+        .into_iter()
+        .map(|(religion, _)| religion.to_string().into())
+        .collect()
+}
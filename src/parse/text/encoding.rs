@@ -0,0 +1,89 @@
+use std::fmt;
+
+/// The encodings text files are found in. The game writes them as UTF-16LE,
+/// but mods often save them again with other editors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Windows1252,
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf16Le => "UTF-16LE",
+            Encoding::Utf16Be => "UTF-16BE",
+            Encoding::Windows1252 => "Windows-1252",
+        })
+    }
+}
+
+/// How many bytes are looked at to guess between UTF-16 byte orders.
+const SAMPLE: usize = 4096;
+
+/// Decodes a text file by its byte order mark. Without one, the encoding is
+/// guessed and returned along with the text.
+pub fn decode(buf: &[u8]) -> (String, Option<Encoding>) {
+    if let Some(rest) = buf.strip_prefix(b"\xef\xbb\xbf") {
+        (decode_as(rest, Encoding::Utf8), None)
+    } else if let Some(rest) = buf.strip_prefix(b"\xff\xfe") {
+        (decode_as(rest, Encoding::Utf16Le), None)
+    } else if let Some(rest) = buf.strip_prefix(b"\xfe\xff") {
+        (decode_as(rest, Encoding::Utf16Be), None)
+    } else {
+        let encoding = guess(buf);
+        (decode_as(buf, encoding), Some(encoding))
+    }
+}
+
+/// Text in these files is mostly ASCII, so UTF-16 shows up as zero bytes on
+/// one side of each pair. Otherwise valid UTF-8 is taken as such, and
+/// anything else is assumed to be in the Windows western code page.
+fn guess(buf: &[u8]) -> Encoding {
+    let sample = &buf[..buf.len().min(SAMPLE)];
+    let pairs = sample.len() / 2;
+    let zeros = |parity| {
+        sample
+            .iter()
+            .skip(parity)
+            .step_by(2)
+            .filter(|b| **b == 0)
+            .count()
+    };
+    let (even, odd) = (zeros(0), zeros(1));
+    if odd > pairs / 4 && odd > even {
+        Encoding::Utf16Le
+    } else if even > pairs / 4 && even > odd {
+        Encoding::Utf16Be
+    } else if std::str::from_utf8(buf).is_ok() {
+        Encoding::Utf8
+    } else {
+        Encoding::Windows1252
+    }
+}
+
+fn decode_as(buf: &[u8], encoding: Encoding) -> String {
+    match encoding {
+        Encoding::Utf8 => String::from_utf8_lossy(buf).into_owned(),
+        Encoding::Utf16Le => String::from_utf16le_lossy(buf),
+        Encoding::Utf16Be => String::from_utf16be_lossy(buf),
+        Encoding::Windows1252 => buf.iter().map(|b| windows_1252(*b)).collect(),
+    }
+}
+
+/// Windows-1252 is Latin-1 with printable characters in place of most of the
+/// C1 controls.
+fn windows_1252(b: u8) -> char {
+    const HIGH: [char; 32] = [
+        '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž',
+        '\u{8f}', '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}',
+        'ž', 'Ÿ',
+    ];
+    match b {
+        0x80..=0x9f => HIGH[(b - 0x80) as usize],
+        _ => b as char,
+    }
+}
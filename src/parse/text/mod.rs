@@ -5,8 +5,11 @@ use anyhow::Result;
 use crate::parse::manifest::ParserMode;
 
 mod bin;
+mod encoding;
 mod txt;
 
+pub use encoding::decode;
+
 pub fn parse_txt(data: impl AsRef<str>, _: ParserMode) -> Result<HashMap<String, String>> {
     txt::parse(data)
 }
@@ -71,10 +71,11 @@ pub fn prepare_generation_arguments(args: Args) -> GenerateArgs {
         file.truncate(n);
         let path = String::from_utf16(&file).expect("invalid file name").into();
         GenerateArgs {
-            manifest: Some(path),
+            manifests: vec![path],
             out_dir: None,
             base_game_path: None,
             serve: true,
+            watch: false,
             deps_file: None,
         }
     }
@@ -0,0 +1,143 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use tokio::fs;
+use tracing::info;
+
+use crate::utils::write_file;
+
+/// The list of generated files, kept in the output directory between runs.
+pub const GENERATED_FILE: &str = ".faust-generated.json";
+
+#[derive(Default, Clone, Serialize, Deserialize)]
+struct Generated {
+    version: String,
+    /// The generated files, with the key of the inputs they were rendered from
+    /// if they can be reused.
+    files: BTreeMap<PathBuf, Option<String>>,
+}
+
+/// Keeps track of the files written to the output directory, so that images
+/// whose inputs have not changed since the last run are not rendered again,
+/// and files that are no longer generated can be removed.
+#[derive(Default, Clone)]
+pub struct RenderCache {
+    out_dir: PathBuf,
+    previous: Generated,
+    current: Generated,
+}
+
+impl RenderCache {
+    pub async fn load(out_dir: &Path) -> Self {
+        let previous = fs::read(out_dir.join(GENERATED_FILE))
+            .await
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default();
+        Self {
+            out_dir: out_dir.to_path_buf(),
+            previous,
+            current: Generated {
+                version: env!("CARGO_PKG_VERSION").into(),
+                files: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// Checks whether the file at `path` was rendered by the last run, or
+    /// already by this one, from the inputs with this key, and keeps it if so.
+    pub fn reuse(&mut self, path: &Path, key: &str) -> bool {
+        let relative = self.relative(path);
+        if self.current.files.get(&relative).and_then(|k| k.as_deref()) == Some(key) {
+            return true;
+        }
+        let previous = self
+            .previous
+            .files
+            .get(&relative)
+            .and_then(|k| k.as_deref());
+        let fresh =
+            self.previous.version == self.current.version && previous == Some(key) && path.exists();
+        if fresh {
+            info!("reused {}", path.display());
+            self.current.files.insert(relative, Some(key.into()));
+        }
+        fresh
+    }
+
+    /// Records a file written to the output directory, with the key of its
+    /// inputs if it can be reused by the next run.
+    pub fn record(&mut self, path: &Path, key: Option<String>) {
+        let relative = self.relative(path);
+        self.current.files.insert(relative, key);
+    }
+
+    fn relative(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.out_dir)
+            .unwrap_or(path)
+            .to_path_buf()
+    }
+
+    /// Removes the files from the previous run that were not generated this
+    /// time, and saves the list of generated files. Returns how many files
+    /// were removed.
+    pub async fn finish(&self) -> Result<usize> {
+        let mut pruned = 0;
+        for path in self.previous.files.keys() {
+            if self.current.files.contains_key(path) {
+                continue;
+            }
+            let full = self.out_dir.join(path);
+            if fs::remove_file(&full).await.is_ok() {
+                info!("removed stale {}", path.display());
+                pruned += 1;
+                for dir in full.ancestors().skip(1) {
+                    if dir == self.out_dir || fs::remove_dir(dir).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+        let data = serde_json::to_vec_pretty(&self.current).context("listing generated files")?;
+        write_file(self.out_dir.join(GENERATED_FILE), data)
+            .await
+            .with_context(|| format!("writing {GENERATED_FILE}"))?;
+        Ok(pruned)
+    }
+}
+
+/// The version of the way images are rendered. Bump it whenever resizing,
+/// cropping, the radar map or anything else changes the rendered files, so
+/// that files from earlier runs are not reused.
+const RENDER_VERSION: u32 = 1;
+
+/// A hash of everything an output file is rendered from.
+pub struct CacheKey(Sha256);
+
+impl CacheKey {
+    pub fn new(kind: &str) -> Self {
+        Self(Sha256::new())
+            .add(RENDER_VERSION.to_le_bytes())
+            .add(kind)
+    }
+
+    pub fn add(mut self, part: impl AsRef<[u8]>) -> Self {
+        let part = part.as_ref();
+        self.0.update((part.len() as u64).to_le_bytes());
+        self.0.update(part);
+        self
+    }
+
+    pub fn finish(self) -> String {
+        self.0
+            .finalize()
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+}
@@ -1,5 +1,7 @@
+mod cache;
 mod renderer;
 mod templates;
 
 pub use renderer::RenderData;
 pub use renderer::Renderer;
+pub use templates::DiffHtml;
@@ -1,20 +1,18 @@
 use std::{
-    collections::{BTreeSet, HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::{self, Display, Formatter, Write as _},
     path::{Component, Path, PathBuf},
 };
 
 use anyhow::{Context as _, Result, anyhow};
 use askama::Template as _;
-use image::{
-    DynamicImage, Rgba, RgbaImage,
-    imageops::{FilterType::Lanczos3, filter3x3, overlay},
-};
+use image::{DynamicImage, Rgba, RgbaImage, imageops::FilterType::Lanczos3};
 use implicit_clone::unsync::IString;
+use indexmap::IndexMap;
 use indicatif::{HumanBytes, MultiProgress, ProgressBar};
 use silphium::{
-    ModuleMap, Route, StaticApp, StaticAppProps,
-    model::{Aor, Era, Faction, Module, Pool, Unit},
+    BASE_META, Catalog, Language, MERCS, Messages, ModuleMap, Route, StaticApp, StaticAppProps,
+    model::{self, CampaignMap, Era, Faction, Module, Unit},
 };
 use tokio::fs;
 use tracing::info;
@@ -23,9 +21,16 @@ use yew_router::Routable as _;
 use crate::{
     args::Config,
     mod_folder::ModFolder,
-    parse::{Region, Sprite, manifest::ParserMode::*},
-    render::templates::{FILESYSTEM_STATIC, IndexHtml, PrefetchHtml, RedirectHtml},
-    utils::{FOLDER, LINK, PAPER, PICTURE, progress_style, read_image, write_file, write_image},
+    parse::{Region, Sprite, Translation, manifest::ParserMode::*},
+    render::{
+        cache::{CacheKey, RenderCache},
+        templates::{FILESYSTEM_STATIC, IndexHtml, PrefetchHtml, RedirectHtml},
+    },
+    serve::RELOAD_PATH,
+    utils::{
+        FOLDER, LINK, PAPER, PICTURE, decode_image, progress_style, read_file, write_file,
+        write_image,
+    },
 };
 
 #[derive(Clone)]
@@ -100,36 +105,74 @@ impl Display for PreloadType {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct RenderData {
     pub regions: HashMap<String, Region>,
     pub sprites: HashMap<String, Sprite>,
     pub culture: String,
+    /// The texts of the module in each of its languages but the first, keyed
+    /// by language.
+    pub translations: IndexMap<IString, Translation>,
+    /// The translations of the site texts in each language of the module.
+    pub messages: HashMap<IString, Messages>,
 }
 
 #[derive(Clone)]
 pub struct Renderer {
     pub cfg: Config,
-    pub folder: ModFolder,
-    pub data: Vec<u8>,
+    pub configs: HashMap<IString, Config>,
+    /// The catalog file of each language, keyed by the path its pages are
+    /// under.
+    pub data: HashMap<String, Vec<u8>>,
     pub modules: ModuleMap,
+    /// The modules in the other languages of the site, keyed by language.
+    pub translations: IndexMap<IString, ModuleMap>,
+    pub languages: Vec<Language>,
+    pub messages: HashMap<IString, Messages>,
     pub render_data: HashMap<IString, RenderData>,
     pub preload: Vec<(String, Preload)>,
+    pub cache: RenderCache,
 }
 
 impl Renderer {
     pub fn new(
-        cfg: &Config,
+        cfgs: &[Config],
         modules: ModuleMap,
         render_data: HashMap<IString, RenderData>,
     ) -> Self {
+        let mut languages: Vec<Language> = vec![];
+        for cfg in cfgs {
+            for (id, language) in cfg.manifest.languages.iter() {
+                if !languages.iter().any(|l| &l.id == id) {
+                    languages.push(Language {
+                        id: id.clone(),
+                        name: language.name.clone(),
+                    });
+                }
+            }
+        }
+        let mut messages: HashMap<IString, Messages> = HashMap::new();
+        for id in modules.keys() {
+            for (language, table) in render_data[id].messages.iter() {
+                let entry = messages.entry(language.clone()).or_default();
+                entry.0.extend(table.0.clone());
+            }
+        }
+
         Self {
-            cfg: cfg.clone(),
-            folder: ModFolder::new(cfg.clone()),
-            data: Vec::new(),
+            cfg: cfgs[0].clone(),
+            configs: cfgs
+                .iter()
+                .map(|cfg| (cfg.manifest.id.clone(), cfg.clone()))
+                .collect(),
+            data: HashMap::new(),
             modules,
+            translations: IndexMap::new(),
+            languages,
+            messages,
             render_data,
             preload: vec![],
+            cache: RenderCache::default(),
         }
     }
 
@@ -138,64 +181,90 @@ impl Renderer {
         self.create_directory(m.clone()).await?;
         self.create_static_files(m.clone()).await?;
         self.render_images(m.clone()).await?;
+        self.translate_modules();
         self.render_data(m.clone()).await?;
         self.render_routes(m.clone()).await?;
+        self.prune_directory(m.clone()).await?;
         let _ = m.clear();
         Ok(())
     }
 
+    /// Writes a file to the output directory, recording it as generated.
+    async fn write_output(
+        &mut self,
+        path: impl AsRef<Path>,
+        contents: impl AsRef<[u8]>,
+    ) -> Result<()> {
+        let path = self.cfg.out_dir.join(path);
+        write_file(&path, contents).await?;
+        self.cache.record(&path, None);
+        Ok(())
+    }
+
     async fn render_images(&mut self, m: MultiProgress) -> Result<()> {
         let pb = m.add(ProgressBar::new_spinner());
         pb.set_style(progress_style());
         pb.tick();
         pb.set_message(format!("{PICTURE}rendering images"));
         for m in self.modules.values_mut() {
+            let cfg = &self.configs[m.id.as_ref()];
+            let folder = ModFolder::new(cfg.clone());
             let extra = &self.render_data[m.id.as_ref()];
-            let src = self.folder.banner_png();
+            let src = folder.banner_png();
             let banner_path = Self::module_banner_path(m);
             let dst = self.cfg.out_dir.join(&banner_path);
             pb.tick();
             pb.set_message(format!("{PICTURE}rendering {}", web_path(&banner_path)));
-            Self::render_image(&self.cfg, &src, &dst, MOD_BANNER_SIZE).await?;
-
-            let radar_map_tga = self.folder.radar_map_tga();
-            let regions_map_path = self.folder.map_regions_tga();
-            let mut areas = read_image(&self.cfg, regions_map_path).await?.into_rgba8();
-            let radar = read_image(&self.cfg, radar_map_tga).await?;
-            let radar = radar
-                .resize_exact(areas.width() * 2, areas.height() * 2, Lanczos3)
-                .into_rgba8();
-            erase_cities_and_ports(&mut areas);
+            Self::render_image(cfg, &mut self.cache, &src, &dst, MOD_BANNER_SIZE).await?;
+
+            let mut map_sources = MapSources::read(cfg, &folder).await?;
+            if !m.regions.is_empty() {
+                let map_path = Self::campaign_map_path(&m.id);
+                let dst = self.cfg.out_dir.join(&map_path);
+                pb.tick();
+                pb.set_message(format!("{PICTURE}rendering {}", web_path(&map_path)));
+                Self::render_radar_map(&mut self.cache, &mut map_sources, &dst).await?;
+
+                let colors: HashMap<_, _> = m
+                    .regions
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, r)| {
+                        let color = extra.regions.get(r.id.as_str())?.color;
+                        Some((Rgba([color.0, color.1, color.2, 0xFF]), i))
+                    })
+                    .collect();
+                let areas = map_sources.areas()?;
+                let mut outlines = trace_outlines(areas, &colors, m.regions.len());
+                m.regions = m
+                    .regions
+                    .iter()
+                    .zip(outlines.iter_mut())
+                    .map(|(r, outline)| model::Region {
+                        outline: std::mem::take(outline).into(),
+                        ..r
+                    })
+                    .collect();
+                m.map = Some(CampaignMap {
+                    image: web_path(&map_path).into(),
+                    width: areas.width(),
+                    height: areas.height(),
+                });
+            }
             let mut rendered_mercs = HashSet::new();
             let mut pools = m.pools.to_vec();
             for p in pools.iter_mut() {
-                let pool_path = Self::pool_path(&m.id, p);
-                let dst = self.cfg.out_dir.join(&pool_path);
-                pb.tick();
-                pb.set_message(format!("{PICTURE}rendering {}", web_path(&pool_path)));
-                Self::render_map(
-                    &radar,
-                    &areas,
-                    &dst,
-                    extra
-                        .regions
-                        .values()
-                        .filter(|r| p.regions.iter().find(|r1| r1.as_str() == r.id).is_some()),
-                    Rgba([0xFF, 0x71, 0x00, 0xC0]),
-                    Rgba([0x00, 0x00, 0x00, 0xFF]),
-                )
-                .await?;
-
                 let mut units = p.units.to_vec();
                 for u in units.iter_mut() {
-                    let src = self.folder.unit_info_tga("mercs", &u.unit.key);
+                    let src = folder.unit_info_tga("mercs", &u.unit.key);
                     let portrait_path = Self::unit_portrait_path(&m.id, "mercs", &mut u.unit);
                     if !rendered_mercs.contains(&u.unit.id) {
                         rendered_mercs.insert(u.unit.id.clone());
                         let dst = self.cfg.out_dir.join(&portrait_path);
                         pb.tick();
                         pb.set_message(format!("{PICTURE}rendering {}", web_path(&portrait_path)));
-                        Self::render_image(&self.cfg, &src, &dst, UNIT_PORTRAIT_SIZE).await?;
+                        Self::render_image(cfg, &mut self.cache, &src, &dst, UNIT_PORTRAIT_SIZE)
+                            .await?;
                     }
                 }
                 p.units = units.into();
@@ -203,79 +272,60 @@ impl Renderer {
             m.pools = pools.into();
 
             for e in m.eras.values_mut() {
-                let src = self.cfg.manifest_dir.join(e.icon.as_ref());
+                let src = cfg.manifest_dir.join(e.icon.as_ref());
                 let icon_path = Self::era_icon_path(&m.id, e);
                 let dst = self.cfg.out_dir.join(&icon_path);
                 pb.tick();
                 pb.set_message(format!("{PICTURE}rendering {}", web_path(&icon_path)));
-                Self::render_image(&self.cfg, &src, &dst, ERA_ICON_SIZE).await?;
+                Self::render_image(cfg, &mut self.cache, &src, &dst, ERA_ICON_SIZE).await?;
 
-                let src = self.cfg.manifest_dir.join(e.icoff.as_ref());
+                let src = cfg.manifest_dir.join(e.icoff.as_ref());
                 let icoff_path = Self::era_icoff_path(&m.id, e);
                 let dst = self.cfg.out_dir.join(&icoff_path);
                 pb.tick();
                 pb.set_message(format!("{PICTURE}rendering {}", web_path(&icoff_path)));
-                Self::render_image(&self.cfg, &src, &dst, ERA_ICON_SIZE).await?;
+                Self::render_image(cfg, &mut self.cache, &src, &dst, ERA_ICON_SIZE).await?;
             }
 
-            let mut rendered_aors: HashSet<BTreeSet<IString>> = HashSet::new();
             for f in m.factions.values_mut() {
                 let image_key = f.image.clone();
                 let symbol_path = Self::faction_symbol_path(&m.id, f);
                 let dst = self.cfg.out_dir.join(&symbol_path);
                 pb.tick();
                 pb.set_message(format!("{PICTURE}rendering {}", web_path(&symbol_path)));
-                match self.cfg.manifest.mode {
+                match cfg.manifest.mode {
                     Original | Remastered => {
-                        let src = self.folder.faction_symbol_tga(image_key.as_str());
-                        Self::render_image(&self.cfg, &src, &dst, FACTION_SYMBOL_SIZE).await?;
+                        let src = folder.faction_symbol_tga(image_key.as_str());
+                        Self::render_image(cfg, &mut self.cache, &src, &dst, FACTION_SYMBOL_SIZE)
+                            .await?;
                     }
                     Medieval2 => {
                         let sprite = &extra
                             .sprites
                             .get(image_key.as_str())
                             .ok_or_else(|| anyhow!("missing sprite {image_key}"))?;
-                        let src = self
-                            .folder
-                            .ui_culture_spritesheet_tga(&extra.culture, &sprite.file);
-                        Self::render_sprite(&self.cfg, &src, &dst, sprite, FACTION_SYMBOL_SIZE)
-                            .await?;
+                        let src = folder.ui_culture_spritesheet_tga(&extra.culture, &sprite.file);
+                        Self::render_sprite(
+                            cfg,
+                            &mut self.cache,
+                            &src,
+                            &dst,
+                            sprite,
+                            FACTION_SYMBOL_SIZE,
+                        )
+                        .await?;
                     }
                 }
 
-                let mut aors = f.aors.to_vec();
-                for aor in aors.iter_mut() {
-                    let aor_path = Self::aor_path(&m.id, rendered_aors.len(), aor);
-                    let region_set = aor.regions.iter().map(|s| s).collect();
-                    if rendered_aors.contains(&region_set) {
-                        continue;
-                    }
-                    let dst = self.cfg.out_dir.join(&aor_path);
-                    pb.tick();
-                    pb.set_message(format!("{PICTURE}rendering {}", web_path(&aor_path)));
-                    Self::render_map(
-                        &radar,
-                        &areas,
-                        &dst,
-                        extra.regions.values().filter(|r| {
-                            aor.regions.iter().find(|r1| r1.as_str() == r.id).is_some()
-                        }),
-                        Rgba([0xFF, 0x71, 0x00, 0xC0]),
-                        Rgba([0x00, 0x00, 0x00, 0xFF]),
-                    )
-                    .await?;
-                    rendered_aors.insert(region_set);
-                }
-                f.aors = aors.into();
-
                 let mut roster: Vec<_> = f.roster.iter().collect();
                 for u in roster.iter_mut() {
-                    let src = self.folder.unit_info_tga(&f.id, &u.key);
+                    let src = folder.unit_info_tga(&f.id, &u.key);
                     let portrait_path = Self::unit_portrait_path(&m.id, &f.id, u);
                     let dst = self.cfg.out_dir.join(&portrait_path);
                     pb.tick();
                     pb.set_message(format!("{PICTURE}rendering {}", web_path(&portrait_path)));
-                    Self::render_image(&self.cfg, &src, &dst, UNIT_PORTRAIT_SIZE).await?;
+                    Self::render_image(cfg, &mut self.cache, &src, &dst, UNIT_PORTRAIT_SIZE)
+                        .await?;
                 }
                 f.roster = roster.into();
             }
@@ -284,6 +334,31 @@ impl Renderer {
         Ok(())
     }
 
+    /// Makes the modules in the other languages of the site from the rendered
+    /// ones, so that they share their images. Modules without a language are
+    /// shown as they are in every language.
+    fn translate_modules(&mut self) {
+        self.translations = self
+            .languages
+            .iter()
+            .skip(1)
+            .map(|l| {
+                let modules = self
+                    .modules
+                    .iter()
+                    .map(|(id, m)| {
+                        let module = match self.render_data[id].translations.get(&l.id) {
+                            Some(translation) => translation.apply(m),
+                            None => m.clone(),
+                        };
+                        (id.clone(), module)
+                    })
+                    .collect();
+                (l.id.clone(), modules)
+            })
+            .collect();
+    }
+
     fn module_banner_path(module: &mut Module) -> PathBuf {
         let path = PathBuf::from("images")
             .join(module.id.as_ref())
@@ -333,45 +408,58 @@ impl Renderer {
         path
     }
 
-    fn pool_path(module_id: &str, pool: &mut Pool) -> PathBuf {
-        let path = PathBuf::from("images")
-            .join(module_id)
-            .join("pools")
-            .join(pool.map.as_ref())
-            .with_extension("webp");
-        pool.map = web_path(&path).into();
-        path
-    }
-
-    fn aor_path(module_id: &str, i: usize, aor: &mut Aor) -> PathBuf {
-        let path = PathBuf::from("images")
-            .join(module_id)
-            .join(format!("aors/aor-{}.webp", i + 1));
-        aor.map = web_path(&path).into();
-        path
+    fn campaign_map_path(module_id: &str) -> PathBuf {
+        PathBuf::from("images").join(module_id).join("map.webp")
     }
 
     async fn render_image(
         cfg: &Config,
+        cache: &mut RenderCache,
         from: &Path,
         to: &Path,
         (width, height): (u32, u32),
     ) -> Result<()> {
-        let img = read_image(cfg, from).await?;
+        let buf = read_file(cfg, from)
+            .await
+            .with_context(|| format!("reading image {}", from.display()))?;
+        let key = CacheKey::new("image")
+            .add(&buf)
+            .add(format!("{width}x{height}"))
+            .finish();
+        if cache.reuse(to, &key) {
+            return Ok(());
+        }
+        let img = decode_image(from, buf)?;
         let img = img.resize(width, height, Lanczos3);
         write_image(to, &img).await?;
+        cache.record(to, Some(key));
         info!("rendered {}", to.display());
         Ok(())
     }
 
     async fn render_sprite(
         cfg: &Config,
+        cache: &mut RenderCache,
         from: &Path,
         to: &Path,
         sprite: &Sprite,
         (width, height): (u32, u32),
     ) -> Result<()> {
-        let mut sheet = read_image(cfg, from).await?;
+        let buf = read_file(cfg, from)
+            .await
+            .with_context(|| format!("reading image {}", from.display()))?;
+        let key = CacheKey::new("sprite")
+            .add(&buf)
+            .add(format!(
+                "{},{},{},{}",
+                sprite.left, sprite.top, sprite.right, sprite.bottom
+            ))
+            .add(format!("{width}x{height}"))
+            .finish();
+        if cache.reuse(to, &key) {
+            return Ok(());
+        }
+        let mut sheet = decode_image(from, buf)?;
         let img = sheet
             .crop(
                 sprite.left as _,
@@ -381,44 +469,23 @@ impl Renderer {
             )
             .resize(width, height, Lanczos3);
         write_image(to, &img).await?;
+        cache.record(to, Some(key));
         info!("rendered {}", to.display());
         Ok(())
     }
 
-    async fn render_map<'a>(
-        radar: &RgbaImage,
-        areas: &RgbaImage,
+    async fn render_radar_map(
+        cache: &mut RenderCache,
+        sources: &mut MapSources,
         to: &Path,
-        regions: impl IntoIterator<Item = &'a Region>,
-        color: Rgba<u8>,
-        border_color: Rgba<u8>,
     ) -> Result<()> {
-        let mut image = DynamicImage::from(radar.clone());
-        let regions: Vec<_> = regions.into_iter().collect();
-        let regions = regions.into_iter();
-        let mut blots = DynamicImage::from(RgbaImage::from_pixel(
-            areas.width(),
-            areas.height(),
-            Rgba([0, 0, 0, 0]),
-        ));
-        for region in regions {
-            let mut blot = RgbaImage::from_pixel(areas.width(), areas.height(), Rgba([0, 0, 0, 0]));
-            let mut border =
-                RgbaImage::from_pixel(areas.width(), areas.height(), Rgba([0, 0, 0, 0]));
-            let region_color = Rgba([region.color.0, region.color.1, region.color.2, 0xFF]);
-            areas
-                .enumerate_pixels()
-                .filter_map(|(x, y, p)| (region_color == *p).then_some((x, y)))
-                .for_each(|(x, y)| {
-                    blot.put_pixel(x, y, color);
-                    border.put_pixel(x, y, border_color)
-                });
-            overlay(&mut blots, &blot, 0, 0);
-            overlay(&mut blots, &filter3x3(&border, EDGE_KERNEL), 0, 0);
+        let key = CacheKey::new("map").add(&sources.key).finish();
+        if cache.reuse(to, &key) {
+            return Ok(());
         }
-        let blots = blots.resize(image.width(), image.height(), Lanczos3);
-        overlay(&mut image, &blots, 0, 0);
-        write_image(to, &DynamicImage::from(image)).await?;
+        let radar = sources.radar()?;
+        write_image(to, &DynamicImage::from(radar)).await?;
+        cache.record(to, Some(key));
         info!("rendered {}", to.display());
         Ok(())
     }
@@ -428,34 +495,61 @@ impl Renderer {
         pb.set_style(progress_style());
         pb.tick();
         pb.set_message(format!("{PAPER}rendering catalog data"));
-        let mut data = vec![];
-        ciborium::into_writer(&self.modules, &mut data).context("generating catalog file")?;
-        self.data = data.clone();
-        write_file(&self.cfg.out_dir.join("mods.cbor"), data)
-            .await
-            .context("writing mods.cbor")?;
-        self.preload
-            .push(("/mods.cbor".into(), PreloadType::Cbor.into()));
+        let catalogs: Vec<_> = std::iter::once((None, &self.modules))
+            .chain(self.translations.iter().map(|(id, m)| (Some(id), m)))
+            .map(|(translation, modules)| {
+                let language = translation
+                    .or(self.languages.first().map(|l| &l.id))
+                    .cloned()
+                    .unwrap_or_default();
+                let catalog = Catalog {
+                    modules: modules.clone(),
+                    messages: self.messages.get(&language).cloned().unwrap_or_default(),
+                    language,
+                    languages: self.languages.iter().cloned().collect(),
+                };
+                (language_base(translation.map(|id| id.as_str())), catalog)
+            })
+            .collect();
+        let mut size = 0;
+        for (base, catalog) in catalogs {
+            let mut data = vec![];
+            ciborium::into_writer(&catalog, &mut data).context("generating catalog file")?;
+            let path = PathBuf::from(base.trim_start_matches('/')).join("mods.cbor");
+            size += data.len();
+            self.write_output(&path, &data)
+                .await
+                .with_context(|| format!("writing {}", path.display()))?;
+            self.data.insert(base, data);
+        }
         pb.finish_with_message(format!(
             "{PAPER}rendered catalog ({})",
-            HumanBytes(self.data.len() as u64)
+            HumanBytes(size as u64)
         ));
         info!("rendered catalog");
         Ok(())
     }
 
-    async fn render_routes(&self, m: MultiProgress) -> Result<()> {
+    async fn render_routes(&mut self, m: MultiProgress) -> Result<()> {
         let pb = m.add(ProgressBar::new_spinner());
         pb.set_style(progress_style());
         pb.tick();
         pb.set_message(format!("{LINK}rendering routes"));
-        let routes = collect_routes(&self.modules);
+        let mut routes = collect_routes(&self.modules);
+        for (id, modules) in self.translations.iter() {
+            routes.extend(
+                collect_routes(modules)
+                    .into_iter()
+                    .filter(|r| r.route != Route::NotFound)
+                    .map(|r| r.in_language(id)),
+            );
+        }
         for r in &routes {
             pb.tick();
             pb.set_message(format!("{LINK}rendering {}", r.route.to_path()));
             if let Some(ref target) = r.redirect {
-                write_file(
-                    &self.cfg.out_dir.join(&r.path),
+                self.write_output(
+                    &r.path,
                     RedirectHtml { target }.render().with_context(|| {
                         format!("rendering redirect {} -> {}", r.route.to_path(), target)
                     })?,
@@ -463,12 +557,12 @@ impl Renderer {
                 .await
                 .with_context(|| format!("writing file {}", r.path.display()))?;
             } else {
-                let body = &self.render_route(r.route.clone()).await;
+                let body = &self.render_route(r).await;
                 let head = &self
                     .render_preload(r)
                     .with_context(|| format!("rendering preloads for {}", r.route.to_path()))?;
-                write_file(
-                    &self.cfg.out_dir.join(&r.path),
+                self.write_output(
+                    &r.path,
                     IndexHtml { head, body }.render().with_context(|| {
                         format!("rendering {} -> {}", r.route.to_path(), r.path.display())
                     })?,
@@ -482,10 +576,11 @@ impl Renderer {
         Ok(())
     }
 
-    async fn render_route(&self, route: Route) -> String {
+    async fn render_route(&self, r: &RenderRoute) -> String {
         let props = StaticAppProps {
-            route,
-            data: self.data.clone().into(),
+            route: r.route.clone(),
+            data: self.data[&r.base].clone().into(),
+            base: r.base.clone().into(),
         };
         let renderer = yew::LocalServerRenderer::<StaticApp>::with_props(props);
         let string = renderer.render().await;
@@ -494,47 +589,82 @@ impl Renderer {
 
     fn render_preload(&self, r: &RenderRoute) -> Result<String> {
         let mut preload = self.preload.clone();
+        preload.push((format!("{}/mods.cbor", r.base), PreloadType::Cbor.into()));
         preload.extend_from_slice(&r.preload);
-        let string = PrefetchHtml { preload: &preload }.render()?;
+        let mut string = PrefetchHtml { preload: &preload }.render()?;
+        if !r.base.is_empty() {
+            let _ = write!(
+                &mut string,
+                r#"<meta name="{BASE_META}" content="{}">"#,
+                r.base
+            );
+        }
+        if self.cfg.watch {
+            let _ = write!(
+                &mut string,
+                r#"<script>new EventSource("{RELOAD_PATH}").addEventListener("reload", () => location.reload())</script>"#
+            );
+        }
         Ok(string)
     }
 
-    async fn create_directory(&self, m: MultiProgress) -> Result<()> {
+    async fn create_directory(&mut self, m: MultiProgress) -> Result<()> {
         let pb = m.add(ProgressBar::new_spinner());
         pb.set_style(progress_style());
-        if self.cfg.out_dir.exists() {
-            pb.tick();
-            pb.set_message(format!("{FOLDER}clearing output directory"));
-            fs::remove_dir_all(&self.cfg.out_dir)
-                .await
-                .with_context(|| {
-                    format!("clearing output directory {}", self.cfg.out_dir.display())
-                })?;
-        }
         pb.tick();
         pb.set_message(format!("{FOLDER}creating output directory"));
         fs::create_dir_all(&self.cfg.out_dir)
             .await
             .with_context(|| format!("creating output directory {}", self.cfg.out_dir.display()))?;
+        self.cache = RenderCache::load(&self.cfg.out_dir).await;
         pb.finish_with_message(format!("{FOLDER}created {}", self.cfg.out_dir.display()));
         info!("created output directory");
         Ok(())
     }
 
+    async fn prune_directory(&self, m: MultiProgress) -> Result<()> {
+        let pb = m.add(ProgressBar::new_spinner());
+        pb.set_style(progress_style());
+        pb.tick();
+        pb.set_message(format!("{FOLDER}removing stale files"));
+        let pruned = self.cache.finish().await?;
+        pb.finish_with_message(format!("{FOLDER}removed {pruned} stale files"));
+        info!("removed {pruned} stale files");
+        Ok(())
+    }
+
     async fn create_static_files(&mut self, m: MultiProgress) -> Result<()> {
         let pb = m.add(ProgressBar::new_spinner());
         pb.set_style(progress_style());
         pb.tick();
         pb.set_message(format!("{PAPER}writing faust.yml"));
-        write_file(&self.cfg.out_dir.join("faust.yml"), &self.cfg.manifest.raw)
-            .await
-            .context("writing faust.yml")?;
+        if self.configs.len() == 1 {
+            let manifest = self.cfg.manifest.raw.clone();
+            self.write_output("faust.yml", manifest)
+                .await
+                .context("writing faust.yml")?;
+        } else {
+            let mut site = String::from("modules:\n");
+            let ids: Vec<_> = self.modules.keys().cloned().collect();
+            for id in ids {
+                let manifest_path = PathBuf::from(id.as_str()).join("faust.yml");
+                let manifest = self.configs[&id].manifest.raw.clone();
+                self.write_output(&manifest_path, manifest)
+                    .await
+                    .with_context(|| format!("writing {}", manifest_path.display()))?;
+                let _ = writeln!(&mut site, "  - {id}/faust.yml");
+            }
+            self.write_output("faust.yml", site)
+                .await
+                .context("writing faust.yml")?;
+        }
         for file in FILESYSTEM_STATIC {
             pb.tick();
             pb.set_message(format!("{PAPER}creating {}", file.path));
             file.create(&self.cfg.out_dir)
                 .await
                 .with_context(|| format!("creating {}", file.path))?;
+            self.cache.record(&self.cfg.out_dir.join(file.path), None);
             info!("created static {}", file.path);
             if let Some(preload_as) = file.preload_as {
                 self.preload.push((file.path.into(), preload_as.into()));
@@ -566,6 +696,26 @@ pub struct RenderRoute {
     pub redirect: Option<String>,
     pub path: PathBuf,
     pub preload: Vec<(String, Preload)>,
+    /// The path the pages of the route's language are under.
+    pub base: String,
+}
+
+impl RenderRoute {
+    /// The same route, in a language other than the first.
+    fn in_language(self, id: &str) -> Self {
+        let base = language_base(Some(id));
+        Self {
+            path: PathBuf::from(id).join(self.path),
+            redirect: self.redirect.map(|target| format!("{base}{target}")),
+            base,
+            ..self
+        }
+    }
+}
+
+/// The path the pages of a language are under, with none for the first.
+fn language_base(language: Option<&str>) -> String {
+    language.map(|id| format!("/{id}")).unwrap_or_default()
 }
 
 fn collect_routes(modules: &ModuleMap) -> Vec<RenderRoute> {
@@ -575,12 +725,14 @@ fn collect_routes(modules: &ModuleMap) -> Vec<RenderRoute> {
         path: "index.html".into(),
         redirect: None,
         preload: vec![],
+        base: String::new(),
     });
     routes.push(RenderRoute {
         route: Route::NotFound,
         path: "404.html".into(),
         redirect: None,
         preload: vec![],
+        base: String::new(),
     });
 
     for module in modules.values() {
@@ -598,6 +750,31 @@ fn collect_routes(modules: &ModuleMap) -> Vec<RenderRoute> {
             vec![],
         ));
 
+        routes.push(prepare_route(
+            Route::Compare {
+                module: module.id.clone(),
+            },
+            vec![],
+        ));
+
+        if module.map.is_some() {
+            routes.push(prepare_route(
+                Route::Map {
+                    module: module.id.clone(),
+                },
+                vec![],
+            ));
+        }
+
+        if !module.buildings.is_empty() {
+            routes.push(prepare_route(
+                Route::Buildings {
+                    module: module.id.clone(),
+                },
+                vec![],
+            ));
+        }
+
         for faction in module.factions.values() {
             let id_or_alias = faction.id_or_alias();
             let route: Route = Route::Faction {
@@ -614,11 +791,169 @@ fn collect_routes(modules: &ModuleMap) -> Vec<RenderRoute> {
                 ));
             }
             routes.push(prepare_route(route, vec![]));
+            routes.push(prepare_route(
+                Route::Army {
+                    module: module.id.clone(),
+                    faction: id_or_alias.clone(),
+                },
+                vec![],
+            ));
+
+            let mut keys = HashSet::new();
+            for unit in faction.roster.iter().filter(|u| keys.insert(u.key.clone())) {
+                routes.push(prepare_route(
+                    Route::Unit {
+                        module: module.id.clone(),
+                        faction: id_or_alias.clone(),
+                        unit: unit.key.clone(),
+                    },
+                    vec![],
+                ));
+            }
+        }
+
+        let mut keys = HashSet::new();
+        for entry in module.pools.iter().flat_map(|p| p.units.iter()) {
+            if keys.insert(entry.unit.key.clone()) {
+                routes.push(prepare_route(
+                    Route::Unit {
+                        module: module.id.clone(),
+                        faction: MERCS.into(),
+                        unit: entry.unit.key.clone(),
+                    },
+                    vec![],
+                ));
+            }
         }
     }
     routes
 }
 
+/// The images area maps are drawn on, decoded only once a map has to be
+/// rendered.
+struct MapSources {
+    radar_path: PathBuf,
+    radar: Vec<u8>,
+    areas_path: PathBuf,
+    areas: Vec<u8>,
+    key: String,
+    decoded_areas: Option<RgbaImage>,
+}
+
+impl MapSources {
+    async fn read(cfg: &Config, folder: &ModFolder) -> Result<Self> {
+        let radar_path = folder.radar_map_tga();
+        let radar = read_file(cfg, &radar_path)
+            .await
+            .with_context(|| format!("reading image {}", radar_path.display()))?;
+        let areas_path = folder.map_regions_tga();
+        let areas = read_file(cfg, &areas_path)
+            .await
+            .with_context(|| format!("reading image {}", areas_path.display()))?;
+        let key = CacheKey::new("map sources")
+            .add(&radar)
+            .add(&areas)
+            .finish();
+        Ok(Self {
+            radar_path,
+            radar,
+            areas_path,
+            areas,
+            key,
+            decoded_areas: None,
+        })
+    }
+
+    /// The region map, with cities and ports painted in the color of their
+    /// region.
+    fn areas(&mut self) -> Result<&RgbaImage> {
+        if self.decoded_areas.is_none() {
+            let mut areas = decode_image(&self.areas_path, self.areas.clone())?.into_rgba8();
+            erase_cities_and_ports(&mut areas);
+            self.decoded_areas = Some(areas);
+        }
+        Ok(self.decoded_areas.as_ref().expect("decoded region map"))
+    }
+
+    /// The radar map, scaled to twice the size of the region map. This is
+    /// only needed when the map image is not in the cache.
+    fn radar(&mut self) -> Result<RgbaImage> {
+        let (width, height) = self.areas()?.dimensions();
+        let radar = decode_image(&self.radar_path, self.radar.clone())?;
+        Ok(radar
+            .resize_exact(width * 2, height * 2, Lanczos3)
+            .into_rgba8())
+    }
+}
+
+/// Traces the outline of each region on the region map as an SVG path, in
+/// pixels of the region map. The regions are numbered by their colors.
+fn trace_outlines(
+    areas: &RgbaImage,
+    colors: &HashMap<Rgba<u8>, usize>,
+    count: usize,
+) -> Vec<String> {
+    // Collect the edges between each region and its neighbors, going clockwise
+    let mut edges = vec![BTreeMap::<(u32, u32), Vec<(u32, u32)>>::new(); count];
+    let same = |x: i64, y: i64, p: &Rgba<u8>| {
+        x >= 0
+            && y >= 0
+            && (x as u32) < areas.width()
+            && (y as u32) < areas.height()
+            && areas.get_pixel(x as u32, y as u32) == p
+    };
+    for (x, y, p) in areas.enumerate_pixels() {
+        let Some(&region) = colors.get(p) else {
+            continue;
+        };
+        let (xi, yi) = (x as i64, y as i64);
+        let sides = [
+            (same(xi, yi - 1, p), (x, y), (x + 1, y)),
+            (same(xi + 1, yi, p), (x + 1, y), (x + 1, y + 1)),
+            (same(xi, yi + 1, p), (x + 1, y + 1), (x, y + 1)),
+            (same(xi - 1, yi, p), (x, y + 1), (x, y)),
+        ];
+        for (_, from, to) in sides.into_iter().filter(|(inside, ..)| !inside) {
+            edges[region].entry(from).or_default().push(to);
+        }
+    }
+
+    // Chain the edges into closed loops, keeping only the corners
+    edges
+        .into_iter()
+        .map(|mut edges| {
+            let mut path = String::new();
+            while let Some((&start, _)) = edges.first_key_value() {
+                write!(path, "M{} {}", start.0, start.1).unwrap();
+                let mut at = start;
+                let mut horizontal = None;
+                while let Some(next) = edges.get_mut(&at).and_then(Vec::pop) {
+                    if edges[&at].is_empty() {
+                        edges.remove(&at);
+                    }
+                    let is_horizontal = next.1 == at.1;
+                    if horizontal == Some(is_horizontal) {
+                        // extend the previous segment
+                        path.truncate(path.rfind(['H', 'V']).unwrap_or(path.len()));
+                    }
+                    if is_horizontal {
+                        write!(path, "H{}", next.0).unwrap();
+                    } else {
+                        write!(path, "V{}", next.1).unwrap();
+                    }
+                    horizontal = Some(is_horizontal);
+                    at = next;
+                    if at == start {
+                        break;
+                    }
+                }
+                path.push('Z');
+            }
+            path
+        })
+        .collect()
+}
+
 fn erase_cities_and_ports(image: &mut RgbaImage) {
     let copy = image.clone();
     for (x, y, p) in image.enumerate_pixels_mut() {
@@ -661,6 +996,7 @@ fn prepare_route(route: Route, preload: Vec<(String, Preload)>) -> RenderRoute {
         route,
         redirect: None,
         preload,
+        base: String::new(),
     }
 }
 
@@ -673,6 +1009,7 @@ fn prepare_redirect(from: Route, to: Route) -> RenderRoute {
         route: from,
         redirect: Some(target),
         preload: vec![],
+        base: String::new(),
     }
 }
 
@@ -681,8 +1018,6 @@ const ERA_ICON_SIZE: (u32, u32) = (64, 64);
 const FACTION_SYMBOL_SIZE: (u32, u32) = (128, 128);
 const UNIT_PORTRAIT_SIZE: (u32, u32) = (82, 112);
 
-const EDGE_KERNEL: &[f32] = &[-1.0, -1.0, -1.0, -1.0, 8.0, -1.0, -1.0, -1.0, -1.0];
-
 const WATER_COLOR: Rgba<u8> = Rgba([0x29, 0x8C, 0xE9, 0xFF]);
 const CITY_COLOR: Rgba<u8> = Rgba([0x00, 0x00, 0x00, 0xFF]);
 const PORT_COLOR: Rgba<u8> = Rgba([0xFF, 0xFF, 0xFF, 0xFF]);
@@ -4,6 +4,7 @@ use anyhow::Result;
 use askama::Template;
 
 use crate::{
+    diff::Report,
     render::renderer::{Preload, PreloadType},
     utils::write_file,
 };
@@ -27,6 +28,12 @@ pub struct PrefetchHtml<'a> {
     pub preload: &'a [(String, Preload)],
 }
 
+#[derive(Template)]
+#[template(path = "diff.html")]
+pub struct DiffHtml<'a> {
+    pub report: &'a Report,
+}
+
 pub struct StaticFile<'a> {
     pub path: &'a str,
     pub contents: &'a [u8],
@@ -259,6 +266,14 @@ pub const FILESYSTEM_STATIC: &[StaticFile] = &[
         )),
         preload_as: None,
     },
+    StaticFile {
+        path: "icons/ui/compare.svg",
+        contents: include_bytes!(concat!(
+            env!("OUT_DIR"),
+            "/silphium_template/icons/ui/compare.svg"
+        )),
+        preload_as: None,
+    },
     StaticFile {
         path: "images/ui/example-unit.webp",
         contents: include_bytes!(concat!(
@@ -1,15 +1,26 @@
-use std::net::SocketAddr;
+use std::{convert::Infallible, net::SocketAddr};
 
 use anyhow::{Context as _, Result};
-use axum::Router;
+use axum::{
+    Router,
+    response::sse::{Event, KeepAlive, Sse},
+    routing::get,
+};
 use clipboard_rs::{Clipboard as _, ClipboardContext};
 use console::style;
-use tokio::net::TcpListener;
+use futures_util::{Stream, stream};
+use tokio::{
+    net::TcpListener,
+    sync::broadcast::{self, error::RecvError},
+};
 use tower_http::services::{ServeDir, ServeFile};
 
 use crate::{args::Config, utils::EARTH};
 
-pub async fn serve(cfg: &Config) -> Result<()> {
+/// Where pages generated in watch mode listen for reload events.
+pub const RELOAD_PATH: &str = "/_faust/reload";
+
+pub async fn serve(cfg: &Config, reload: Option<broadcast::Sender<()>>) -> Result<()> {
     let addr = SocketAddr::from(([127, 0, 0, 1], PORT));
     let listener = TcpListener::bind(addr)
         .await
@@ -27,9 +38,20 @@ pub async fn serve(cfg: &Config) -> Result<()> {
         style(format!("Browse your site at {}", style(url).cyan().bold())).green(),
         style("(this has been copied to your clipboard)").dim()
     );
+
+    let mut router = Router::new();
+    if let Some(reload) = reload {
+        router = router.route(
+            RELOAD_PATH,
+            get(move || {
+                let events = reload_events(reload.subscribe());
+                async move { Sse::new(events).keep_alive(KeepAlive::default()) }
+            }),
+        );
+    }
     axum::serve(
         listener,
-        Router::new().fallback_service(
+        router.fallback_service(
             ServeDir::new(&cfg.out_dir)
                 .not_found_service(ServeFile::new(&cfg.out_dir.join("404.html"))),
         ),
@@ -39,4 +61,16 @@ pub async fn serve(cfg: &Config) -> Result<()> {
     Ok(())
 }
 
+fn reload_events(rx: broadcast::Receiver<()>) -> impl Stream<Item = Result<Event, Infallible>> {
+    stream::unfold(rx, |mut rx| async move {
+        loop {
+            match rx.recv().await {
+                Ok(()) => return Some((Ok(Event::default().event("reload").data("")), rx)),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
 const PORT: u16 = 7777;
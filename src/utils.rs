@@ -24,6 +24,10 @@ pub async fn read_image(cfg: &Config, path: impl AsRef<Path>) -> Result<DynamicI
     let buf = read_file(cfg, from)
         .await
         .with_context(|| format!("reading image {}", from.display()))?;
+    decode_image(from, buf)
+}
+
+pub fn decode_image(from: &Path, buf: Vec<u8>) -> Result<DynamicImage> {
     let format = ImageFormat::from_path(from)
         .with_context(|| format!("selecting image format for {}", from.display()))?;
     ImageReader::with_format(Cursor::new(buf), format)
@@ -54,16 +58,38 @@ pub async fn write_file(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> R
         .with_context(|| format!("creating {}", path.display()))?)
 }
 
+/// Reads a mod file, from the base game archives if there is no such loose
+/// file.
 pub async fn read_file(cfg: &Config, path: impl AsRef<Path>) -> Result<Vec<u8>> {
     let path = path.as_ref();
+    let found = cfg.files.find(path);
+    if found.is_none()
+        && let Some(entry) = cfg.packs.find(&cfg.fallback_dir, path)
+    {
+        add_dep(cfg, entry.archive())?;
+        return entry.read().await.with_context(|| {
+            format!(
+                "reading {} from {}",
+                path.display(),
+                entry.archive().display()
+            )
+        });
+    }
+    let path = found.as_deref().unwrap_or(path);
+    add_dep(cfg, path)?;
+    Ok(fs::read(path)
+        .await
+        .with_context(|| format!("reading {}", path.display()))?)
+}
+
+fn add_dep(cfg: &Config, path: &Path) -> Result<()> {
+    cfg.deps.add(path);
     if let Some(dep) = &cfg.deps_file {
         let mut file = OpenOptions::new().append(true).open(dep)?;
         use std::io::Write as _;
         writeln!(file, "{}", path.display())?;
     }
-    Ok(fs::read(path)
-        .await
-        .with_context(|| format!("reading {}", path.display()))?)
+    Ok(())
 }
 
 pub fn progress_style() -> ProgressStyle {
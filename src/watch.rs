@@ -0,0 +1,89 @@
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Result;
+use console::style;
+use tokio::{fs, sync::broadcast, time::sleep};
+use tracing::info;
+
+use crate::{
+    args::{Config, GenerateArgs},
+    generate,
+    serve::serve,
+    utils::THINKING,
+};
+
+/// Serves the site and regenerates it whenever one of the files read while
+/// generating it changes, or a file is added where one was looked for,
+/// telling the open pages to reload.
+pub async fn watch(args: GenerateArgs, cfgs: Vec<Config>) -> Result<()> {
+    let (reload, _) = broadcast::channel(16);
+    let cfg = cfgs[0].clone();
+    tokio::select! {
+        res = serve(&cfg, Some(reload.clone())) => res,
+        res = watch_files(args, cfgs, reload) => res,
+    }
+}
+
+async fn watch_files(
+    args: GenerateArgs,
+    mut cfgs: Vec<Config>,
+    reload: broadcast::Sender<()>,
+) -> Result<()> {
+    let mut stamps = stamp(&cfgs).await;
+    loop {
+        sleep(POLL_INTERVAL).await;
+        let current = stamp(&cfgs).await;
+        let changed: Vec<_> = current
+            .iter()
+            .filter(|(path, time)| stamps.get(*path) != Some(time))
+            .map(|(path, _)| path)
+            .collect();
+        if changed.is_empty() {
+            continue;
+        }
+        for path in &changed {
+            info!("changed {}", path.display());
+        }
+        println!(
+            "{THINKING}{}",
+            style(match changed.as_slice() {
+                [path] => format!("{} changed, regenerating", path.display()),
+                _ => format!("{} files changed, regenerating", changed.len()),
+            })
+            .yellow()
+        );
+
+        match regenerate(&args).await {
+            Ok(new_cfgs) => {
+                cfgs = new_cfgs;
+                let _ = reload.send(());
+            }
+            Err(e) => eprintln!("{} {e:?}", style("error:").red().bold()),
+        }
+        // on failure the old set of files is still watched, so fixing the
+        // broken file triggers another attempt
+        stamps = stamp(&cfgs).await;
+    }
+}
+
+async fn regenerate(args: &GenerateArgs) -> Result<Vec<Config>> {
+    let cfgs = Config::get(args.clone())?;
+    generate(&cfgs).await?;
+    Ok(cfgs)
+}
+
+async fn stamp(cfgs: &[Config]) -> HashMap<PathBuf, Option<SystemTime>> {
+    let mut stamps = HashMap::new();
+    let deps = &cfgs[0].deps;
+    for path in deps.paths().into_iter().chain(deps.dirs()) {
+        let time = fs::metadata(&path).await.and_then(|m| m.modified()).ok();
+        stamps.insert(path, time);
+    }
+    stamps
+}
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);